@@ -0,0 +1,15 @@
+pub mod attribution;
+pub mod class_hierarchy;
+pub mod class_loader_leaks;
+pub mod class_loaders;
+pub mod detectors;
+pub mod duplicate_classes;
+pub mod duplicate_strings;
+pub mod event_listeners;
+pub mod packages;
+pub mod platform;
+pub mod players;
+pub mod plugin_dependencies;
+pub mod plugins;
+pub mod scheduler;
+pub mod worlds;
@@ -0,0 +1,108 @@
+use crate::analysis::class_loaders::owning_loader;
+use crate::analysis::plugins::Plugin;
+use crate::heap_dump::{Edge, HeapDump, ReferenceFilter, FAKE_ROOT_ID};
+use hprof_rs::hprof_model::U8;
+use std::collections::HashMap;
+use std::iter::successors;
+
+/// The plugin that is responsible for keeping an object alive.
+#[derive(Clone, PartialEq)]
+pub struct Attribution {
+    pub plugin_main_class_object_id: U8,
+    /// the object or class of the plugin that (indirectly) holds on to the attributed object
+    pub holder_object_id: U8,
+    /// the reference of the holder that leads to the attributed object,
+    /// unknown if the holder was found in the dominator tree
+    pub edge: Option<Edge>,
+}
+
+/// Attributes an object to the first plugin on a path from a GC root to it. If there is no plugin
+/// on the path, the closest object of a plugin that dominates the object is used instead.
+/// `plugins` maps class loaders to their plugin, see [crate::analysis::plugins::plugins_by_loader].
+pub fn attribute(
+    heap_dump: &HeapDump,
+    plugins: &HashMap<U8, Plugin>,
+    object_id: U8,
+    path: &[U8],
+    filter: ReferenceFilter,
+) -> Option<Attribution> {
+    let plugin_of =
+        |object_id: U8| owning_loader(heap_dump, object_id).and_then(|loader| plugins.get(&loader));
+
+    let on_path = path.iter().enumerate().find_map(|(index, holder)| {
+        plugin_of(*holder).map(|plugin| Attribution {
+            plugin_main_class_object_id: plugin.main_class_object_id,
+            holder_object_id: *holder,
+            edge: path
+                .get(index + 1)
+                .and_then(|next| heap_dump.object_graph.edge_weight(*holder, *next))
+                .copied(),
+        })
+    });
+    if on_path.is_some() {
+        return on_path;
+    }
+
+    let immediate_dominators = &heap_dump.dominator_tree(filter).immediate_dominators;
+    successors(immediate_dominators.get(&object_id).copied(), |dominator| {
+        immediate_dominators.get(dominator).copied()
+    })
+    .take_while(|dominator| *dominator != FAKE_ROOT_ID)
+    .find_map(|dominator| {
+        plugin_of(dominator).map(|plugin| Attribution {
+            plugin_main_class_object_id: plugin.main_class_object_id,
+            holder_object_id: dominator,
+            edge: None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::plugins::PluginDescription;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn attribute_prefers_the_path_and_falls_back_to_dominators() {
+        let mut builder = HeapDumpBuilder::new();
+        let loader_class = builder.class("java/lang/ClassLoader", 0, 0, &[]);
+        let loader = builder.instance(loader_class, vec![]);
+        let main_class = builder.class("example/Main", 0, loader, &[]);
+        let holder_class = builder.class("example/Holder", 0, loader, &["list"]);
+        let server_class = builder.class("Server", 0, 0, &["holder"]);
+        let list_class = builder.class("List", 0, 0, &["value"]);
+        let target = builder.instance(list_class, vec![object(0)]);
+        let list = builder.instance(list_class, vec![object(target)]);
+        let holder = builder.instance(holder_class, vec![object(list)]);
+        let server = builder.instance(server_class, vec![object(holder)]);
+        builder.root(server);
+        let heap_dump = builder.build();
+
+        let plugin = Plugin {
+            object_id: 0,
+            main_class_object_id: main_class,
+            class_loader_object_id: loader,
+            enabled: true,
+            description: PluginDescription::default(),
+        };
+        let plugins = HashMap::from([(loader, plugin)]);
+
+        let path = [server, holder, list, target];
+        let attribution = attribute(&heap_dump, &plugins, target, &path, ReferenceFilter::All);
+        let attribution = attribution.unwrap();
+        assert_eq!(attribution.plugin_main_class_object_id, main_class);
+        assert_eq!(attribution.holder_object_id, holder);
+        assert_eq!(
+            attribution.edge.as_ref(),
+            heap_dump.object_graph.edge_weight(holder, list)
+        );
+
+        let attribution = attribute(&heap_dump, &plugins, target, &[], ReferenceFilter::All);
+        let attribution = attribution.unwrap();
+        assert_eq!(attribution.holder_object_id, holder);
+        assert_eq!(attribution.edge, None);
+
+        assert!(attribute(&heap_dump, &plugins, holder, &[], ReferenceFilter::All).is_none());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::heap_dump::HeapDump;
+use hprof_rs::hprof_model::U8;
+use std::collections::HashMap;
+
+/// Instance counts and sizes of a class, both of the class itself and
+/// including all of its (transitive) subclasses.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct HierarchyStats {
+    pub instance_count: usize,
+    pub shallow_size: u64,
+    pub inclusive_instance_count: usize,
+    pub inclusive_shallow_size: u64,
+}
+
+/// The inheritance tree of all classes in a heap dump.
+pub struct ClassHierarchy {
+    /// classes without a (known) superclass, usually only `java/lang/Object`
+    pub roots: Vec<U8>,
+    /// the direct subclasses of each class, largest inclusive shallow size first
+    pub subclasses: HashMap<U8, Vec<U8>>,
+    pub stats: HashMap<U8, HierarchyStats>,
+}
+
+impl ClassHierarchy {
+    pub fn new(heap_dump: &HeapDump) -> ClassHierarchy {
+        let mut roots = Vec::new();
+        let mut subclasses: HashMap<U8, Vec<U8>> = HashMap::new();
+        let mut stats = HashMap::with_capacity(heap_dump.classes.len());
+        for class_info in heap_dump.classes.values() {
+            if heap_dump
+                .classes
+                .contains_key(&class_info.super_class_object_id)
+            {
+                subclasses
+                    .entry(class_info.super_class_object_id)
+                    .or_default()
+                    .push(class_info.class_object_id);
+            } else {
+                roots.push(class_info.class_object_id);
+            }
+            let instances = heap_dump.instances(class_info.class_object_id);
+            let shallow_size = instances
+                .iter()
+                .map(|instance| instance.shallow_size())
+                .sum();
+            stats.insert(
+                class_info.class_object_id,
+                HierarchyStats {
+                    instance_count: instances.len(),
+                    shallow_size,
+                    inclusive_instance_count: instances.len(),
+                    inclusive_shallow_size: shallow_size,
+                },
+            );
+        }
+
+        // add up the inclusive stats bottom-up, subclasses are always visited before their superclass
+        let mut stack = roots.iter().map(|root| (*root, false)).collect::<Vec<_>>();
+        while let Some((class_id, subclasses_visited)) = stack.pop() {
+            let Some(children) = subclasses.get(&class_id) else {
+                continue;
+            };
+            if subclasses_visited {
+                let (count, size) = children.iter().filter_map(|child| stats.get(child)).fold(
+                    (0, 0),
+                    |(count, size), child| {
+                        (
+                            count + child.inclusive_instance_count,
+                            size + child.inclusive_shallow_size,
+                        )
+                    },
+                );
+                if let Some(class_stats) = stats.get_mut(&class_id) {
+                    class_stats.inclusive_instance_count += count;
+                    class_stats.inclusive_shallow_size += size;
+                }
+            } else {
+                stack.push((class_id, true));
+                stack.extend(children.iter().map(|child| (*child, false)));
+            }
+        }
+
+        let inclusive_size = |class_id: &U8| {
+            stats
+                .get(class_id)
+                .map(|stats| stats.inclusive_shallow_size)
+                .unwrap_or(0)
+        };
+        for children in subclasses.values_mut() {
+            children.sort_by_key(|child| std::cmp::Reverse(inclusive_size(child)));
+        }
+        roots.sort_by_key(|root| std::cmp::Reverse(inclusive_size(root)));
+
+        ClassHierarchy {
+            roots,
+            subclasses,
+            stats,
+        }
+    }
+
+    pub fn subclasses(&self, class_object_id: U8) -> &[U8] {
+        self.subclasses
+            .get(&class_object_id)
+            .map(|subclasses| subclasses.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn stats(&self, class_object_id: U8) -> HierarchyStats {
+        self.stats
+            .get(&class_object_id)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::HeapDumpBuilder;
+    use hprof_rs::hprof_model::Value;
+
+    #[test]
+    fn inclusive_stats_add_up_all_subclasses() {
+        let mut builder = HeapDumpBuilder::new();
+        let object_class = builder.class("java/lang/Object", 0, 0, &[]);
+        let entity = builder.class("example/Entity", object_class, 0, &["id"]);
+        let mob = builder.class("example/Mob", entity, 0, &["health"]);
+        let zombie = builder.class("example/Zombie", mob, 0, &[]);
+        let item = builder.class("example/Item", entity, 0, &[]);
+        builder.instance(entity, vec![Value::Int(1)]);
+        builder.instance(mob, vec![Value::Int(20), Value::Int(2)]);
+        for _ in 0..3 {
+            builder.instance(zombie, vec![Value::Int(20), Value::Int(3)]);
+        }
+        let heap_dump = builder.build();
+        let size = |class_object_id| {
+            heap_dump
+                .instances(class_object_id)
+                .iter()
+                .map(|instance| instance.shallow_size())
+                .sum::<u64>()
+        };
+
+        let hierarchy = ClassHierarchy::new(&heap_dump);
+        assert_eq!(hierarchy.roots, [object_class]);
+        assert_eq!(hierarchy.subclasses(object_class), [entity]);
+        // the larger subclass comes first
+        assert_eq!(hierarchy.subclasses(entity), [mob, item]);
+        assert_eq!(hierarchy.subclasses(zombie), []);
+
+        assert_eq!(
+            hierarchy.stats(zombie),
+            HierarchyStats {
+                instance_count: 3,
+                shallow_size: size(zombie),
+                inclusive_instance_count: 3,
+                inclusive_shallow_size: size(zombie),
+            }
+        );
+        assert_eq!(hierarchy.stats(mob).inclusive_instance_count, 4);
+        assert_eq!(
+            hierarchy.stats(mob).inclusive_shallow_size,
+            size(mob) + size(zombie)
+        );
+        let entity_stats = hierarchy.stats(entity);
+        assert_eq!(entity_stats.instance_count, 1);
+        assert_eq!(entity_stats.inclusive_instance_count, 5);
+        assert_eq!(
+            entity_stats.inclusive_shallow_size,
+            size(entity) + size(mob) + size(zombie)
+        );
+        assert_eq!(hierarchy.stats(object_class).inclusive_instance_count, 5);
+        assert_eq!(hierarchy.stats(item), HierarchyStats::default());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::analysis::class_loaders::{class_loaders, defining_loader, ClassLoaderInfo};
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use hprof_rs::hprof_model::U8;
+use std::collections::{HashSet, VecDeque};
+
+/// A kind of class loader that is kept reachable by a registry for as long as it is in use,
+/// e.g. the loader of a plugin that is referenced by the plugin manager.
+pub struct LoaderRegistry {
+    /// the class (or a superclass) of the managed loaders
+    pub loader_class: &'static str,
+    /// classes whose instances reference the loaders that are currently in use
+    pub registry_classes: &'static [&'static str],
+}
+
+pub const LOADER_REGISTRIES: &[LoaderRegistry] = &[
+    LoaderRegistry {
+        loader_class: "org/bukkit/plugin/java/PluginClassLoader",
+        registry_classes: &[
+            "org/bukkit/plugin/java/JavaPluginLoader",
+            "org/bukkit/plugin/SimplePluginManager",
+            "io/papermc/paper/plugin/manager/PaperPluginInstanceManager",
+        ],
+    },
+    LoaderRegistry {
+        loader_class: "io/papermc/paper/plugin/entrypoint/classloader/PaperPluginClassLoader",
+        registry_classes: &[
+            "io/papermc/paper/plugin/manager/PaperPluginInstanceManager",
+            "io/papermc/paper/plugin/entrypoint/classloader/group/PaperPluginClassLoaderStorage",
+        ],
+    },
+];
+
+/// The base class of plugin instances, see [registered_loaders].
+const PLUGIN_CLASS: &str = "org/bukkit/plugin/java/JavaPlugin";
+
+/// How many references a registered loader may be away from its registry,
+/// e.g. manager -> list -> array -> plugin -> loader.
+const MAX_REGISTRY_DEPTH: usize = 8;
+
+/// Packages of the objects that may sit between a registry and its loaders.
+const REGISTRY_INTERNAL_PACKAGES: &[&str] = &[
+    "java/util/",
+    "org/bukkit/plugin/",
+    "io/papermc/paper/plugin/",
+];
+
+/// A managed class loader that is no longer referenced by its registry,
+/// but is still kept alive by instances of its classes.
+#[derive(Clone)]
+pub struct ClassLoaderLeak {
+    pub loader: ClassLoaderInfo,
+    /// the number of reachable instances of classes defined by the loader
+    pub live_instance_count: usize,
+}
+
+/// Finds managed class loaders (see [LOADER_REGISTRIES]) that are not referenced by any
+/// of their registries, but whose classes still have live instances.
+/// Loaders that retain the most memory come first.
+pub fn class_loader_leaks(heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<ClassLoaderLeak> {
+    let registered = registered_loaders(heap_dump);
+    class_loaders(heap_dump, filter)
+        .into_iter()
+        .filter(|loader| is_managed_loader(heap_dump, loader.object_id))
+        .filter(|loader| !registered.contains(&loader.object_id))
+        .filter_map(|loader| {
+            let live_instance_count = loader
+                .defined_classes
+                .iter()
+                .flat_map(|class_object_id| heap_dump.instances(*class_object_id))
+                .filter(|instance| {
+                    heap_dump
+                        .retained_size(instance.object_id(), filter)
+                        .is_some()
+                })
+                .count();
+            (live_instance_count > 0).then_some(ClassLoaderLeak {
+                loader,
+                live_instance_count,
+            })
+        })
+        .collect()
+}
+
+pub fn is_managed_loader(heap_dump: &HeapDump, object_id: U8) -> bool {
+    heap_dump
+        .class_of(object_id)
+        .is_some_and(|class_object_id| {
+            LOADER_REGISTRIES
+                .iter()
+                .any(|registry| heap_dump.is_subclass_of(class_object_id, registry.loader_class))
+        })
+}
+
+/// Whether the search for registered loaders may continue through an object,
+/// i.e. whether it is a collection or part of the plugin management.
+fn is_registry_internal(heap_dump: &HeapDump, object_id: U8) -> bool {
+    heap_dump
+        .class_of(object_id)
+        .is_some_and(|class_object_id| {
+            let name = heap_dump.class_name(class_object_id);
+            name.starts_with('[')
+                || REGISTRY_INTERNAL_PACKAGES
+                    .iter()
+                    .any(|package| name.starts_with(package))
+        })
+}
+
+/// The managed loaders that are strongly referenced by one of their registries,
+/// directly or through collections. A registered plugin instance registers the loader
+/// that defined its class, but the search does not continue through its fields: a live
+/// plugin holding on to the instance of an unloaded plugin must not make the old loader
+/// look registered.
+pub fn registered_loaders(heap_dump: &HeapDump) -> HashSet<U8> {
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    for class_info in heap_dump.classes.values() {
+        let name = heap_dump.class_name(class_info.class_object_id);
+        if LOADER_REGISTRIES
+            .iter()
+            .any(|registry| registry.registry_classes.contains(&name))
+        {
+            for instance in heap_dump.instances(class_info.class_object_id) {
+                if visited.insert(instance.object_id()) {
+                    queue.push_back((instance.object_id(), 0));
+                }
+            }
+        }
+    }
+
+    let mut registered = HashSet::new();
+    while let Some((object_id, depth)) = queue.pop_front() {
+        if depth == MAX_REGISTRY_DEPTH {
+            continue;
+        }
+        for (_, target, edge) in heap_dump.object_graph.edges(object_id) {
+            if !edge.kind.is_strong() || !visited.insert(target) {
+                continue;
+            }
+            if is_managed_loader(heap_dump, target) {
+                registered.insert(target);
+            } else if let Some(loader) = plugin_loader(heap_dump, target) {
+                registered.insert(loader);
+            } else if is_registry_internal(heap_dump, target) {
+                queue.push_back((target, depth + 1));
+            }
+        }
+    }
+    registered
+}
+
+/// The managed loader that defined the class of a plugin instance.
+fn plugin_loader(heap_dump: &HeapDump, object_id: U8) -> Option<U8> {
+    let class_object_id = heap_dump.class_of(object_id)?;
+    if !heap_dump.is_subclass_of(class_object_id, PLUGIN_CLASS) {
+        return None;
+    }
+    defining_loader(heap_dump, object_id).filter(|loader| is_managed_loader(heap_dump, *loader))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn plugins_do_not_register_the_loaders_they_reference() {
+        let mut builder = HeapDumpBuilder::new();
+        let java_plugin = builder.class(PLUGIN_CLASS, 0, 0, &["classLoader"]);
+        let loader_class = builder.class("org/bukkit/plugin/java/PluginClassLoader", 0, 0, &[]);
+        let manager_class =
+            builder.class("org/bukkit/plugin/SimplePluginManager", 0, 0, &["plugin"]);
+
+        let live_loader = builder.instance(loader_class, vec![]);
+        let live_main = builder.class("live/Main", java_plugin, live_loader, &["old"]);
+        let old_loader = builder.instance(loader_class, vec![]);
+        let old_main = builder.class("old/Main", java_plugin, old_loader, &[]);
+        let old_plugin = builder.instance(old_main, vec![object(old_loader)]);
+        let live_plugin =
+            builder.instance(live_main, vec![object(old_plugin), object(live_loader)]);
+        let manager = builder.instance(manager_class, vec![object(live_plugin)]);
+        builder.root(manager);
+        let heap_dump = builder.build();
+
+        assert_eq!(registered_loaders(&heap_dump), HashSet::from([live_loader]));
+        let leaks = class_loader_leaks(&heap_dump, ReferenceFilter::All);
+        assert_eq!(leaks.len(), 1);
+        assert_eq!(leaks[0].loader.object_id, old_loader);
+        assert_eq!(leaks[0].live_instance_count, 1);
+    }
+
+    #[test]
+    fn paper_plugin_loaders_are_registered_in_their_storage() {
+        let mut builder = HeapDumpBuilder::new();
+        let loader_class = builder.class(
+            "io/papermc/paper/plugin/entrypoint/classloader/PaperPluginClassLoader",
+            0,
+            0,
+            &[],
+        );
+        let storage_class = builder.class(
+            "io/papermc/paper/plugin/entrypoint/classloader/group/PaperPluginClassLoaderStorage",
+            0,
+            0,
+            &["loader"],
+        );
+        let loader = builder.instance(loader_class, vec![]);
+        let storage = builder.instance(storage_class, vec![object(loader)]);
+        builder.root(storage);
+        let heap_dump = builder.build();
+
+        assert!(is_managed_loader(&heap_dump, loader));
+        assert_eq!(registered_loaders(&heap_dump), HashSet::from([loader]));
+    }
+}
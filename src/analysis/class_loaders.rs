@@ -0,0 +1,117 @@
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use hprof_rs::hprof_model::U8;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// The id used for the bootstrap class loader, which is not represented by an object.
+pub const BOOTSTRAP_LOADER_ID: U8 = 0;
+
+/// A class loader together with the classes it defined and the memory used by their instances.
+#[derive(Clone)]
+pub struct ClassLoaderInfo {
+    /// the loader instance, or [BOOTSTRAP_LOADER_ID]
+    pub object_id: U8,
+    pub parent_object_id: Option<U8>,
+    pub defined_classes: Vec<U8>,
+    pub instance_count: usize,
+    pub shallow_size: u64,
+    pub retained_size: u64,
+}
+
+/// Finds all class loaders, including loaders that did not define any classes,
+/// ordered by the retained size of the instances of their classes.
+pub fn class_loaders(heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<ClassLoaderInfo> {
+    let mut loaders: HashMap<U8, ClassLoaderInfo> = HashMap::new();
+    let new_loader = |object_id: U8| ClassLoaderInfo {
+        object_id,
+        parent_object_id: heap_dump.reference_field(object_id, "parent"),
+        defined_classes: Vec::new(),
+        instance_count: 0,
+        shallow_size: 0,
+        retained_size: 0,
+    };
+
+    for class_info in heap_dump.classes.values() {
+        if heap_dump.is_subclass_of(class_info.class_object_id, "java/lang/ClassLoader") {
+            for instance in heap_dump.instances(class_info.class_object_id) {
+                let object_id = instance.object_id();
+                loaders
+                    .entry(object_id)
+                    .or_insert_with(|| new_loader(object_id));
+            }
+        }
+        let instances = heap_dump.instances(class_info.class_object_id);
+        let loader = loaders
+            .entry(class_info.class_loader_object_id)
+            .or_insert_with(|| new_loader(class_info.class_loader_object_id));
+        loader.defined_classes.push(class_info.class_object_id);
+        loader.instance_count += instances.len();
+        loader.shallow_size += instances
+            .iter()
+            .map(|instance| instance.shallow_size())
+            .sum::<u64>();
+    }
+
+    let retained_sizes =
+        heap_dump.retained_size_by_group(filter, |object_id| defining_loader(heap_dump, object_id));
+    for (loader_id, retained_size) in retained_sizes {
+        if let Some(loader) = loaders.get_mut(&loader_id) {
+            loader.retained_size = retained_size;
+        }
+    }
+
+    let mut loaders = loaders.into_values().collect::<Vec<_>>();
+    loaders.sort_by_key(|loader| Reverse(loader.retained_size));
+    loaders
+}
+
+/// The loader that defined the class of an object. Primitive arrays and classes have none.
+pub fn defining_loader(heap_dump: &HeapDump, object_id: U8) -> Option<U8> {
+    heap_dump
+        .class_of(object_id)
+        .and_then(|class_object_id| heap_dump.classes.get(&class_object_id))
+        .map(|class_info| class_info.class_loader_object_id)
+}
+
+/// The loader that defined a class, or, for any other object, the loader that defined its class.
+pub fn owning_loader(heap_dump: &HeapDump, object_id: U8) -> Option<U8> {
+    match heap_dump.classes.get(&object_id) {
+        Some(class_info) => Some(class_info.class_loader_object_id),
+        None => defining_loader(heap_dump, object_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+    use hprof_rs::hprof_model::Value;
+
+    #[test]
+    fn class_loaders_sum_up_the_instances_of_their_classes() {
+        let mut builder = HeapDumpBuilder::new();
+        let loader_class = builder.class("java/lang/ClassLoader", 0, 0, &["parent"]);
+        let loader = builder.instance(loader_class, vec![object(0)]);
+        let cache_class = builder.class("Cache", 0, loader, &["entry"]);
+        let entry_class = builder.class("Entry", 0, loader, &["value"]);
+        let entry = builder.instance(entry_class, vec![Value::Int(1)]);
+        let cache = builder.instance(cache_class, vec![object(entry)]);
+        builder.root(cache);
+        let heap_dump = builder.build();
+
+        let loaders = class_loaders(&heap_dump, ReferenceFilter::All);
+        let info = loaders
+            .iter()
+            .find(|info| info.object_id == loader)
+            .unwrap();
+        assert_eq!(info.defined_classes.len(), 2);
+        assert_eq!(info.instance_count, 2);
+        assert_eq!(info.shallow_size, 24 + 20);
+        // the entry is retained by the cache and only counted once, the loader itself is only
+        // reachable through the classes of the cache and the entry
+        assert_eq!(info.retained_size, 24 + 20 + 24);
+        assert_eq!(loaders[0].object_id, loader);
+        assert_eq!(defining_loader(&heap_dump, entry), Some(loader));
+        assert_eq!(owning_loader(&heap_dump, cache_class), Some(loader));
+    }
+}
@@ -0,0 +1,146 @@
+mod class_loader_leak;
+mod disabled_plugin;
+mod offline_player_map;
+mod player_leak;
+
+use crate::analysis::attribution::{attribute, Attribution};
+use crate::analysis::platform::PlatformProfile;
+use crate::analysis::plugins::plugins_by_loader;
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use class_loader_leak::ClassLoaderLeakDetector;
+use disabled_plugin::DisabledPluginDetector;
+use hprof_rs::hprof_model::U8;
+use offline_player_map::OfflinePlayerMapDetector;
+use player_leak::PlayerLeakDetector;
+
+/// How urgently a finding should be looked at.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Warning => "Warning",
+            Severity::Critical => "Critical",
+        }
+    }
+}
+
+/// An object a finding is about, together with the evidence for it.
+#[derive(Clone, PartialEq)]
+pub struct Suspect {
+    pub object_id: U8,
+    /// `None` if the object is not reachable with the filter the detector ran with
+    pub retained_size: Option<u64>,
+    /// a path from a GC root to the object, showing why it is still alive
+    pub path: Option<Vec<U8>>,
+    /// the main classes of the plugins whose objects are on the path
+    pub plugins: Vec<U8>,
+    /// the plugin responsible for the object, filled in by [run_detectors]
+    pub attribution: Option<Attribution>,
+}
+
+impl Suspect {
+    /// A suspect with its shortest path from a GC root as evidence.
+    pub fn new(heap_dump: &HeapDump, object_id: U8, filter: ReferenceFilter) -> Suspect {
+        Suspect {
+            object_id,
+            retained_size: heap_dump.retained_size(object_id, filter),
+            path: heap_dump.path_to_root(object_id, filter),
+            plugins: Vec::new(),
+            attribution: None,
+        }
+    }
+}
+
+/// A problem found by a detector.
+#[derive(Clone, PartialEq)]
+pub struct Finding {
+    pub severity: Severity,
+    pub title: String,
+    pub description: String,
+    pub suspects: Vec<Suspect>,
+}
+
+/// A check for a known kind of problem, e.g. a typical memory leak of a framework.
+pub trait Detector {
+    fn name(&self) -> &'static str;
+
+    /// Whether the detector makes sense for a heap dump, e.g. whether the classes it inspects were loaded.
+    fn is_applicable(&self, heap_dump: &HeapDump) -> bool;
+
+    fn detect(&self, heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<Finding>;
+}
+
+/// The findings of a single detector.
+#[derive(Clone)]
+pub struct DetectorResult {
+    pub name: &'static str,
+    pub findings: Vec<Finding>,
+}
+
+/// User provided settings of the detectors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectorSettings {
+    /// patterns for the class names of loaders whose objects are part of the server,
+    /// see [PlayerLeakDetector]
+    pub trusted_loaders: Vec<String>,
+}
+
+impl DetectorSettings {
+    /// The settings that suit a platform.
+    pub fn for_platform(profile: &PlatformProfile) -> DetectorSettings {
+        DetectorSettings {
+            trusted_loaders: profile
+                .trusted_loaders
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// All known detectors.
+pub fn detectors(settings: &DetectorSettings) -> Vec<Box<dyn Detector>> {
+    vec![
+        Box::new(PlayerLeakDetector {
+            trusted_loaders: settings.trusted_loaders.clone(),
+        }),
+        Box::new(ClassLoaderLeakDetector),
+        Box::new(DisabledPluginDetector),
+        Box::new(OfflinePlayerMapDetector),
+    ]
+}
+
+/// Runs all detectors that are applicable to the heap dump and attributes their suspects to plugins.
+pub fn run_detectors(
+    heap_dump: &HeapDump,
+    filter: ReferenceFilter,
+    settings: &DetectorSettings,
+) -> Vec<DetectorResult> {
+    let plugins = plugins_by_loader(heap_dump);
+    detectors(settings)
+        .iter()
+        .filter(|detector| detector.is_applicable(heap_dump))
+        .map(|detector| {
+            let mut findings = detector.detect(heap_dump, filter);
+            for suspect in findings
+                .iter_mut()
+                .flat_map(|finding| finding.suspects.iter_mut())
+            {
+                let path = suspect.path.as_deref().unwrap_or_default();
+                suspect.attribution =
+                    attribute(heap_dump, &plugins, suspect.object_id, path, filter);
+            }
+            DetectorResult {
+                name: detector.name(),
+                findings,
+            }
+        })
+        .collect()
+}
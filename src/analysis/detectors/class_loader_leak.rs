@@ -0,0 +1,40 @@
+use crate::analysis::class_loader_leaks::{class_loader_leaks, LOADER_REGISTRIES};
+use crate::analysis::detectors::{Detector, Finding, Severity, Suspect};
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+
+/// Reports plugin class loaders that were unregistered, e.g. by a reload, but are still alive.
+pub struct ClassLoaderLeakDetector;
+
+impl Detector for ClassLoaderLeakDetector {
+    fn name(&self) -> &'static str {
+        "Class loader leaks"
+    }
+
+    fn is_applicable(&self, heap_dump: &HeapDump) -> bool {
+        heap_dump.classes.values().any(|class_info| {
+            let name = heap_dump.class_name(class_info.class_object_id);
+            LOADER_REGISTRIES
+                .iter()
+                .any(|registry| registry.loader_class == name)
+        })
+    }
+
+    fn detect(&self, heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<Finding> {
+        class_loader_leaks(heap_dump, filter)
+            .into_iter()
+            .map(|leak| Finding {
+                severity: Severity::Warning,
+                title: format!(
+                    "Unregistered class loader {}",
+                    heap_dump.describe_object(leak.loader.object_id)
+                ),
+                description: format!(
+                    "The loader is no longer referenced by the plugin manager, but {} live instances \
+                     of its classes keep it alive, retaining {} bytes.",
+                    leak.live_instance_count, leak.loader.retained_size
+                ),
+                suspects: vec![Suspect::new(heap_dump, leak.loader.object_id, filter)],
+            })
+            .collect()
+    }
+}
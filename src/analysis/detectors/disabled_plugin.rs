@@ -0,0 +1,136 @@
+use crate::analysis::class_loader_leaks::{is_managed_loader, registered_loaders};
+use crate::analysis::class_loaders::class_loaders;
+use crate::analysis::detectors::{Detector, Finding, Severity, Suspect};
+use crate::analysis::plugins::plugins;
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+
+/// Reports plugins that were disabled or unregistered, but whose instance is still reachable.
+pub struct DisabledPluginDetector;
+
+impl Detector for DisabledPluginDetector {
+    fn name(&self) -> &'static str {
+        "Disabled plugins"
+    }
+
+    fn is_applicable(&self, heap_dump: &HeapDump) -> bool {
+        heap_dump.classes.values().any(|class_info| {
+            heap_dump.class_name(class_info.class_object_id) == "org/bukkit/plugin/java/JavaPlugin"
+        })
+    }
+
+    fn detect(&self, heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<Finding> {
+        let registered = registered_loaders(heap_dump);
+        let loaders = class_loaders(heap_dump, filter);
+        plugins(heap_dump)
+            .into_iter()
+            .filter(|plugin| heap_dump.retained_size(plugin.object_id, filter).is_some())
+            .filter_map(|plugin| {
+                // only the loaders of known registries can be checked, e.g. not those of mods
+                let loader = plugin.class_loader_object_id;
+                let is_registered =
+                    !is_managed_loader(heap_dump, loader) || registered.contains(&loader);
+                let (severity, state) = match (plugin.enabled, is_registered) {
+                    (true, true) => return None,
+                    (false, true) => (Severity::Info, "is disabled"),
+                    (_, false) => (Severity::Warning, "is no longer registered"),
+                };
+                let name = plugin.description.name.clone().unwrap_or_else(|| {
+                    heap_dump
+                        .class_name(plugin.main_class_object_id)
+                        .to_string()
+                });
+                let retained_size = loaders
+                    .iter()
+                    .find(|loader| loader.object_id == plugin.class_loader_object_id)
+                    .map_or(0, |loader| loader.retained_size);
+                Some(Finding {
+                    severity,
+                    title: format!("Plugin {} {}, but still in memory", name, state),
+                    description: format!(
+                        "The objects of the plugin's class loader retain {} bytes.",
+                        retained_size
+                    ),
+                    suspects: vec![
+                        Suspect::new(heap_dump, plugin.object_id, filter),
+                        Suspect::new(heap_dump, plugin.class_loader_object_id, filter),
+                    ],
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+    use hprof_rs::hprof_model::{Value, U8};
+
+    fn add_plugin(
+        builder: &mut HeapDumpBuilder,
+        java_plugin: U8,
+        loader_class: U8,
+        name: &str,
+    ) -> (U8, U8) {
+        let loader = builder.instance(loader_class, vec![]);
+        let main_class = builder.class(name, java_plugin, loader, &[]);
+        let plugin = builder.instance(main_class, vec![Value::Boolean(true), object(loader)]);
+        (plugin, loader)
+    }
+
+    #[test]
+    fn only_plugins_missing_from_their_registry_are_reported() {
+        let mut builder = HeapDumpBuilder::new();
+        let java_plugin = builder.class(
+            "org/bukkit/plugin/java/JavaPlugin",
+            0,
+            0,
+            &["isEnabled", "classLoader"],
+        );
+        let bukkit_loader_class =
+            builder.class("org/bukkit/plugin/java/PluginClassLoader", 0, 0, &[]);
+        let paper_loader_class = builder.class(
+            "io/papermc/paper/plugin/entrypoint/classloader/PaperPluginClassLoader",
+            0,
+            0,
+            &[],
+        );
+        let custom_loader_class = builder.class("example/CustomClassLoader", 0, 0, &[]);
+        let storage_class = builder.class(
+            "io/papermc/paper/plugin/entrypoint/classloader/group/PaperPluginClassLoaderStorage",
+            0,
+            0,
+            &["loader"],
+        );
+        let holder_class = builder.class("Holder", 0, 0, &["first", "second", "third"]);
+
+        let (paper_plugin, paper_loader) =
+            add_plugin(&mut builder, java_plugin, paper_loader_class, "paper/Main");
+        let (custom_plugin, _) = add_plugin(
+            &mut builder,
+            java_plugin,
+            custom_loader_class,
+            "custom/Main",
+        );
+        let (old_plugin, _) =
+            add_plugin(&mut builder, java_plugin, bukkit_loader_class, "old/Main");
+        let holder = builder.instance(
+            holder_class,
+            vec![
+                object(paper_plugin),
+                object(custom_plugin),
+                object(old_plugin),
+            ],
+        );
+        builder.root(holder);
+        // the storage holds on to the loader of the Paper plugin
+        let storage = builder.instance(storage_class, vec![object(paper_loader)]);
+        builder.root(storage);
+        let heap_dump = builder.build();
+
+        let findings = DisabledPluginDetector.detect(&heap_dump, ReferenceFilter::All);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[0].suspects[0].object_id, old_plugin);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::analysis::class_loaders::owning_loader;
+use crate::analysis::detectors::{Detector, Finding, Severity, Suspect};
+use crate::analysis::players::{known_player_uuids, player_classes, players};
+use crate::analysis::plugins::{plugins_by_loader, Plugin};
+use crate::heap_dump::{HeapDump, ReferenceFilter, FAKE_ROOT_ID};
+use hprof_rs::hprof_model::U8;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+use std::iter::successors;
+
+/// The maps whose entries are decoded. `WeakHashMap`s are left out, as their keys do not keep
+/// the UUIDs alive, and wrappers like `UnmodifiableMap` would report the wrapped map twice.
+const MAP_CLASSES: [&str; 4] = [
+    "java/util/HashMap",
+    "java/util/LinkedHashMap",
+    "java/util/Hashtable",
+    "java/util/concurrent/ConcurrentHashMap",
+];
+
+/// Reports maps of plugins that are keyed by `UUID`s of players who are no longer online,
+/// typically a `HashMap<UUID, ...>` whose entries are not removed when a player quits.
+pub struct OfflinePlayerMapDetector;
+
+impl Detector for OfflinePlayerMapDetector {
+    fn name(&self) -> &'static str {
+        "Maps of offline players"
+    }
+
+    fn is_applicable(&self, heap_dump: &HeapDump) -> bool {
+        !player_classes(heap_dump).is_empty()
+    }
+
+    fn detect(&self, heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<Finding> {
+        let online = players(heap_dump, filter)
+            .into_iter()
+            .filter(|player| player.online)
+            .filter_map(|player| player.uuid)
+            .collect::<HashSet<_>>();
+        // only UUIDs of players the server knows are counted, other UUIDs may identify anything
+        let offline_players = known_player_uuids(heap_dump)
+            .into_iter()
+            .filter(|uuid| !online.contains(uuid))
+            .collect::<HashSet<_>>();
+        let plugins = plugins_by_loader(heap_dump);
+
+        heap_dump
+            .classes
+            .values()
+            .filter(|class_info| {
+                MAP_CLASSES.contains(&heap_dump.class_name(class_info.class_object_id))
+            })
+            .flat_map(|class_info| heap_dump.instances(class_info.class_object_id))
+            .map(|map| map.object_id())
+            .filter_map(|map| {
+                let entries = heap_dump.read_map(map)?;
+                let offline = entries
+                    .iter()
+                    .filter(|(key, _)| heap_dump.type_name(*key) == "java/util/UUID")
+                    .filter_map(|(key, _)| heap_dump.read_uuid(*key))
+                    .filter(|uuid| offline_players.contains(uuid))
+                    .collect::<Vec<_>>();
+                if offline.is_empty() {
+                    return None;
+                }
+
+                // the server keeps offline players in caches, only maps held by plugins are suspicious
+                let holders = plugin_holders(heap_dump, &plugins, map, filter);
+                if holders.is_empty() {
+                    return None;
+                }
+                let mut suspect = Suspect::new(heap_dump, map, filter);
+                suspect.plugins = holders;
+
+                let holder = suspect
+                    .path
+                    .as_ref()
+                    .and_then(|path| path.iter().rev().nth(1))
+                    .map_or("<root>".to_string(), |holder| {
+                        // a static field of a class
+                        if heap_dump.classes.contains_key(holder) {
+                            heap_dump.class_name(*holder).to_string()
+                        } else {
+                            heap_dump.type_name(*holder)
+                        }
+                    });
+                Some(Finding {
+                    severity: Severity::Warning,
+                    title: format!(
+                        "Map in {} holds {} offline players",
+                        holder,
+                        offline.len()
+                    ),
+                    description: format!(
+                        "{} of the {} entries are keyed by UUIDs of players who are not online, e.g. {}.",
+                        offline.len(),
+                        entries.len(),
+                        offline.iter().take(3).join(", ")
+                    ),
+                    suspects: vec![suspect],
+                })
+            })
+            .sorted_by_key(|finding| finding.suspects[0].retained_size)
+            .rev()
+            .collect()
+    }
+}
+
+/// The main classes of the plugins that own an object referencing or dominating the map.
+fn plugin_holders(
+    heap_dump: &HeapDump,
+    plugins: &HashMap<U8, Plugin>,
+    map: U8,
+    filter: ReferenceFilter,
+) -> Vec<U8> {
+    let immediate_dominators = &heap_dump.dominator_tree(filter).immediate_dominators;
+    let dominators = successors(immediate_dominators.get(&map).copied(), |dominator| {
+        immediate_dominators.get(dominator).copied()
+    })
+    .take_while(|dominator| *dominator != FAKE_ROOT_ID);
+    heap_dump
+        .referrers(map)
+        .into_iter()
+        .filter(|(_, edge)| filter.includes(edge.kind))
+        .map(|(referrer, _)| referrer)
+        .chain(dominators)
+        .filter_map(|object_id| owning_loader(heap_dump, object_id))
+        .filter_map(|loader| plugins.get(&loader))
+        .map(|plugin| plugin.main_class_object_id)
+        .unique()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+    use hprof_rs::hprof_model::Value;
+
+    #[test]
+    fn only_uuids_of_known_players_in_plugin_maps_are_reported() {
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("org/bukkit/craftbukkit/entity/CraftPlayer", 0, 0, &[]);
+        let uuid_class = builder.class("java/util/UUID", 0, 0, &["mostSigBits", "leastSigBits"]);
+        let profile_class = builder.class("com/mojang/authlib/GameProfile", 0, 0, &["id", "name"]);
+        let java_plugin = builder.class("org/bukkit/plugin/java/JavaPlugin", 0, 0, &[]);
+        let loader_class = builder.class("org/bukkit/plugin/java/PluginClassLoader", 0, 0, &[]);
+        let server_class = builder.class("net/minecraft/server/players/UserCache", 0, 0, &["map"]);
+
+        let player_uuid = builder.instance(uuid_class, vec![Value::Long(1), Value::Long(2)]);
+        let world_uuid = builder.instance(uuid_class, vec![Value::Long(3), Value::Long(4)]);
+        let profile = builder.instance(profile_class, vec![object(player_uuid), object(0)]);
+        builder.root(profile);
+
+        let loader = builder.instance(loader_class, vec![]);
+        let main_class = builder.class("example/Main", java_plugin, loader, &["cache"]);
+        let plugin_map = builder.hash_map(vec![(player_uuid, 0), (world_uuid, 0)]);
+        let server_map = builder.hash_map(vec![(player_uuid, 0)]);
+        let plugin = builder.instance(main_class, vec![object(plugin_map)]);
+        builder.root(plugin);
+
+        let server = builder.instance(server_class, vec![object(server_map)]);
+        builder.root(server);
+        let heap_dump = builder.build();
+
+        let findings = OfflinePlayerMapDetector.detect(&heap_dump, ReferenceFilter::All);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].title,
+            "Map in example/Main holds 1 offline players"
+        );
+        let suspect = &findings[0].suspects[0];
+        assert_eq!(suspect.object_id, plugin_map);
+        assert_eq!(suspect.plugins, [main_class]);
+    }
+}
@@ -0,0 +1,314 @@
+use crate::analysis::class_loaders::owning_loader;
+use crate::analysis::detectors::{Detector, Finding, Severity, Suspect};
+use crate::analysis::platform::{is_craftbukkit_class, matches_pattern};
+use crate::analysis::players::player_classes;
+use crate::analysis::plugins::plugins_by_loader;
+use crate::heap_dump::{HeapDump, Reference, ReferenceFilter, FAKE_ROOT_ID};
+use hprof_rs::hprof_model::U8;
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// The classes of the server, on Spigot versioned like `net/minecraft/server/v1_16_R3`.
+const SERVER_CLASSES: [&str; 2] = [
+    "net/minecraft/server/MinecraftServer",
+    "net/minecraft/server/v*/MinecraftServer",
+];
+
+/// Reports `CraftPlayer` instances that are kept alive by something other than the server,
+/// typically a plugin that keeps players in a collection after they left.
+pub struct PlayerLeakDetector {
+    /// patterns for the class names of loaders whose objects are part of the server,
+    /// a player is only considered leaked if it is reachable without passing through them.
+    /// The loader of the server classes is always trusted, as e.g. Paperclip loads the server
+    /// with a plain `URLClassLoader` just like the library loaders of plugins.
+    pub trusted_loaders: Vec<String>,
+}
+
+impl Detector for PlayerLeakDetector {
+    fn name(&self) -> &'static str {
+        "Leaking players"
+    }
+
+    fn is_applicable(&self, heap_dump: &HeapDump) -> bool {
+        !player_classes(heap_dump).is_empty()
+    }
+
+    fn detect(&self, heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<Finding> {
+        let trusted_loaders = self.trusted_loader_ids(heap_dump);
+        let predecessors = reachable_outside_server(heap_dump, filter, &trusted_loaders);
+        let plugins = plugins_by_loader(heap_dump);
+
+        let suspects = player_classes(heap_dump)
+            .into_iter()
+            .flat_map(|class_object_id| heap_dump.instances(class_object_id))
+            .map(|instance| instance.object_id())
+            .filter(|object_id| predecessors.contains_key(object_id))
+            .map(|object_id| {
+                let path = path_from_root(&predecessors, object_id);
+                let plugins = path
+                    .iter()
+                    .filter_map(|object_id| owning_loader(heap_dump, *object_id))
+                    .filter_map(|loader| plugins.get(&loader))
+                    .map(|plugin| plugin.main_class_object_id)
+                    .unique()
+                    .collect();
+                Suspect {
+                    object_id,
+                    retained_size: heap_dump.retained_size(object_id, filter),
+                    path: Some(path),
+                    plugins,
+                    attribution: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        if suspects.is_empty() {
+            return Vec::new();
+        }
+
+        let retained_size: u64 = suspects
+            .iter()
+            .filter_map(|suspect| suspect.retained_size)
+            .sum();
+        vec![Finding {
+            severity: Severity::Critical,
+            title: format!("{} leaking players", suspects.len()),
+            description: format!(
+                "These players are reachable without going through objects of the server, \
+                 retaining {} bytes.",
+                retained_size
+            ),
+            suspects,
+        }]
+    }
+}
+
+impl PlayerLeakDetector {
+    /// The loaders whose class matches one of the trusted patterns, and the loaders of the
+    /// server classes.
+    fn trusted_loader_ids(&self, heap_dump: &HeapDump) -> HashSet<U8> {
+        let server_loaders = heap_dump
+            .classes
+            .values()
+            .filter(|class_info| {
+                let name = heap_dump.class_name(class_info.class_object_id);
+                SERVER_CLASSES
+                    .iter()
+                    .any(|pattern| matches_pattern(pattern, name))
+                    || is_craftbukkit_class(name, "CraftServer")
+            })
+            .map(|class_info| class_info.class_loader_object_id)
+            .filter(|loader| *loader != 0);
+        heap_dump
+            .classes
+            .values()
+            .map(|class_info| class_info.class_loader_object_id)
+            .unique()
+            .filter(|loader| {
+                heap_dump.class_of(*loader).is_some_and(|class_object_id| {
+                    let name = heap_dump.class_name(class_object_id);
+                    self.trusted_loaders
+                        .iter()
+                        .any(|pattern| matches_pattern(pattern, name))
+                })
+            })
+            .chain(server_loaders)
+            .collect()
+    }
+}
+
+/// Finds all objects that can be reached from a GC root without passing through
+/// an instance of the server, and maps each of them to its predecessor on such a path.
+fn reachable_outside_server(
+    heap_dump: &HeapDump,
+    filter: ReferenceFilter,
+    trusted_loaders: &HashSet<U8>,
+) -> HashMap<U8, U8> {
+    let mut predecessors = HashMap::new();
+    let mut queue = VecDeque::from([FAKE_ROOT_ID]);
+    while let Some(node) = queue.pop_front() {
+        for (_, target, edge) in heap_dump.object_graph.edges(node) {
+            if !filter.includes(edge.kind) || predecessors.contains_key(&target) {
+                continue;
+            }
+            predecessors.insert(target, node);
+            if !is_server_instance(heap_dump, target, trusted_loaders) {
+                queue.push_back(target);
+            }
+        }
+    }
+    predecessors
+}
+
+/// The path from a GC root to an object found by [reachable_outside_server].
+fn path_from_root(predecessors: &HashMap<U8, U8>, object_id: U8) -> Vec<U8> {
+    let mut path = vec![object_id];
+    let mut current = object_id;
+    while let Some(&predecessor) = predecessors.get(&current) {
+        if predecessor == FAKE_ROOT_ID {
+            break;
+        }
+        path.push(predecessor);
+        current = predecessor;
+    }
+    path.reverse();
+    path
+}
+
+/// Whether an object belongs to the server: an instance or a class defined by a trusted loader,
+/// or one of the trusted loaders itself.
+fn is_server_instance(heap_dump: &HeapDump, object_id: U8, trusted_loaders: &HashSet<U8>) -> bool {
+    if trusted_loaders.contains(&object_id) {
+        return true;
+    }
+    if let Some(class_info) = heap_dump.classes.get(&object_id) {
+        return trusted_loaders.contains(&class_info.class_loader_object_id);
+    }
+    match heap_dump
+        .objects
+        .get(&object_id)
+        .map(|reference| &**reference)
+    {
+        Some(Reference::Instance(instance)) => heap_dump
+            .classes
+            .get(&instance.class_object_id)
+            .is_some_and(|class_info| trusted_loaders.contains(&class_info.class_loader_object_id)),
+        // arrays are only containers, whoever references them owns them
+        Some(Reference::ObjectArray(_)) => false,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::detectors::DetectorSettings;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn players_held_by_the_server_are_not_reported() {
+        let mut builder = HeapDumpBuilder::new();
+        let loader_class = builder.class(
+            "jdk/internal/loader/ClassLoaders$AppClassLoader",
+            0,
+            0,
+            &["classes"],
+        );
+        let app_loader = builder.instance(loader_class, vec![object(0)]);
+        let plugin_loader_class =
+            builder.class("org/bukkit/plugin/java/PluginClassLoader", 0, 0, &[]);
+        let plugin_loader = builder.instance(plugin_loader_class, vec![]);
+        let holder_class = builder.class("java/util/ArrayList", 0, 0, &["first", "second"]);
+        let player_class = builder.class(
+            "org/bukkit/craftbukkit/entity/CraftPlayer",
+            0,
+            app_loader,
+            &[],
+        );
+        let server_class =
+            builder.class("net/minecraft/server/MinecraftServer", 0, app_loader, &[]);
+        let cache_class = builder.class("example/Cache", 0, plugin_loader, &["players"]);
+        let online = builder.instance(player_class, vec![]);
+        let leaked = builder.instance(player_class, vec![]);
+        // the loader's list of classes leads to the statics of the server
+        let classes = builder.instance(holder_class, vec![object(server_class), object(0)]);
+        builder.set_field(app_loader, 0, object(classes));
+        builder.static_field(server_class, "players", object(online));
+        let cache = builder.instance(cache_class, vec![object(leaked)]);
+        builder.root(app_loader);
+        builder.root(server_class);
+        builder.root(cache);
+        let heap_dump = builder.build();
+
+        let detector = PlayerLeakDetector {
+            trusted_loaders: DetectorSettings::for_platform(heap_dump.platform()).trusted_loaders,
+        };
+        let findings = detector.detect(&heap_dump, ReferenceFilter::All);
+        assert_eq!(findings.len(), 1);
+        let suspects = findings[0]
+            .suspects
+            .iter()
+            .map(|suspect| suspect.object_id)
+            .collect::<Vec<_>>();
+        assert_eq!(suspects, [leaked]);
+        assert_eq!(findings[0].suspects[0].path, Some(vec![cache, leaked]));
+    }
+
+    #[test]
+    fn players_held_by_the_game_are_not_reported_on_fabric() {
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("net/fabricmc/loader/api/FabricLoader", 0, 0, &[]);
+        let knot_class = builder.class(
+            "net/fabricmc/loader/impl/launch/knot/KnotClassLoader",
+            0,
+            0,
+            &[],
+        );
+        let knot = builder.instance(knot_class, vec![]);
+        let player_class = builder.class("net/minecraft/server/level/ServerPlayer", 0, knot, &[]);
+        let player_list_class = builder.class(
+            "net/minecraft/server/players/PlayerList",
+            0,
+            knot,
+            &["players"],
+        );
+        let player = builder.instance(player_class, vec![]);
+        let player_list = builder.instance(player_list_class, vec![object(player)]);
+        builder.root(player_list);
+        let heap_dump = builder.build();
+
+        let detector = PlayerLeakDetector {
+            trusted_loaders: DetectorSettings::for_platform(heap_dump.platform()).trusted_loaders,
+        };
+        assert!(detector.detect(&heap_dump, ReferenceFilter::All).is_empty());
+    }
+
+    #[test]
+    fn players_held_by_library_loaders_of_plugins_are_reported() {
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("org/bukkit/Bukkit", 0, 0, &[]);
+        let url_loader_class = builder.class("java/net/URLClassLoader", 0, 0, &[]);
+        let plugin_loader_class = builder.class(
+            "org/bukkit/plugin/java/PluginClassLoader",
+            0,
+            0,
+            &["libraryLoader"],
+        );
+        // Paperclip loads the server and Spigot the libraries of plugins with URLClassLoaders
+        let server_loader = builder.instance(url_loader_class, vec![]);
+        let library_loader = builder.instance(url_loader_class, vec![]);
+        let plugin_loader = builder.instance(plugin_loader_class, vec![object(library_loader)]);
+        let player_class = builder.class(
+            "org/bukkit/craftbukkit/v1_20_R3/entity/CraftPlayer",
+            0,
+            server_loader,
+            &[],
+        );
+        let server_class = builder.class(
+            "org/bukkit/craftbukkit/v1_20_R3/CraftServer",
+            0,
+            server_loader,
+            &[],
+        );
+        let cache_class = builder.class("com/example/lib/Cache", 0, library_loader, &[]);
+        let online = builder.instance(player_class, vec![]);
+        let leaked = builder.instance(player_class, vec![]);
+        builder.static_field(server_class, "players", object(online));
+        builder.static_field(cache_class, "players", object(leaked));
+        builder.root(server_class);
+        builder.root(cache_class);
+        builder.root(plugin_loader);
+        let heap_dump = builder.build();
+
+        let detector = PlayerLeakDetector {
+            trusted_loaders: DetectorSettings::for_platform(heap_dump.platform()).trusted_loaders,
+        };
+        let findings = detector.detect(&heap_dump, ReferenceFilter::All);
+        assert_eq!(findings.len(), 1);
+        let suspects = findings[0]
+            .suspects
+            .iter()
+            .map(|suspect| suspect.object_id)
+            .collect::<Vec<_>>();
+        assert_eq!(suspects, [leaked]);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::heap_dump::HeapDump;
+use hprof_rs::hprof_model::U8;
+use itertools::Itertools;
+use std::cmp::Reverse;
+
+/// A class name that is defined by more than one class loader.
+#[derive(Clone)]
+pub struct DuplicateClass {
+    pub name: String,
+    /// the classes with this name, most instances first
+    pub copies: Vec<ClassCopy>,
+}
+
+#[derive(Clone)]
+pub struct ClassCopy {
+    pub class_object_id: U8,
+    pub class_loader_object_id: U8,
+    pub instance_count: usize,
+}
+
+impl DuplicateClass {
+    pub fn loader_count(&self) -> usize {
+        self.copies
+            .iter()
+            .map(|copy| copy.class_loader_object_id)
+            .unique()
+            .count()
+    }
+}
+
+/// Finds classes with the same name that were defined by different class loaders,
+/// e.g. a library shaded into several plugins. Classes defined by many loaders come first.
+pub fn duplicate_classes(heap_dump: &HeapDump) -> Vec<DuplicateClass> {
+    heap_dump
+        .classes
+        .values()
+        .into_group_map_by(|class_info| heap_dump.class_name(class_info.class_object_id))
+        .into_iter()
+        .map(|(name, classes)| DuplicateClass {
+            name: name.to_string(),
+            copies: classes
+                .into_iter()
+                .map(|class_info| ClassCopy {
+                    class_object_id: class_info.class_object_id,
+                    class_loader_object_id: class_info.class_loader_object_id,
+                    instance_count: heap_dump.instances(class_info.class_object_id).len(),
+                })
+                .sorted_by_key(|copy| Reverse(copy.instance_count))
+                .collect(),
+        })
+        .filter(|duplicate| duplicate.loader_count() > 1)
+        .sorted_by(|a, b| {
+            b.loader_count()
+                .cmp(&a.loader_count())
+                .then_with(|| a.name.cmp(&b.name))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::HeapDumpBuilder;
+
+    #[test]
+    fn classes_are_grouped_by_name_across_loaders() {
+        let mut builder = HeapDumpBuilder::new();
+        let loader_class = builder.class("java/lang/ClassLoader", 0, 0, &[]);
+        let [first, second, third] = [(); 3].map(|_| builder.instance(loader_class, vec![]));
+        // a library shaded into three plugins, one of them loading it twice
+        let gson = [first, second, third, third]
+            .map(|loader| builder.class("com/google/gson/Gson", 0, loader, &[]));
+        builder.instance(gson[1], vec![]);
+        builder.instance(gson[1], vec![]);
+        builder.instance(gson[2], vec![]);
+        // a class shaded into two plugins
+        let [guava_first, guava_second] =
+            [first, second].map(|loader| builder.class("com/google/common/Cache", 0, loader, &[]));
+        builder.instance(guava_second, vec![]);
+        // loaded twice by the same loader, which is no duplicate across loaders
+        builder.class("example/Reloaded", 0, first, &[]);
+        builder.class("example/Reloaded", 0, first, &[]);
+        let heap_dump = builder.build();
+
+        let duplicates = duplicate_classes(&heap_dump);
+        let names = duplicates
+            .iter()
+            .map(|duplicate| (duplicate.name.as_str(), duplicate.loader_count()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [("com/google/gson/Gson", 3), ("com/google/common/Cache", 2)]
+        );
+
+        let copies = |duplicate: &DuplicateClass| {
+            duplicate
+                .copies
+                .iter()
+                .map(|copy| (copy.class_object_id, copy.instance_count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(copies(&duplicates[0]).len(), 4);
+        assert_eq!(copies(&duplicates[0])[..2], [(gson[1], 2), (gson[2], 1)]);
+        assert_eq!(
+            copies(&duplicates[1]),
+            [(guava_second, 1), (guava_first, 0)]
+        );
+    }
+}
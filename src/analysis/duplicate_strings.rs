@@ -0,0 +1,83 @@
+use crate::heap_dump::HeapDump;
+use hprof_rs::hprof_model::U8;
+use itertools::Itertools;
+use std::cmp::Reverse;
+
+/// A string value that is held by more than one `java.lang.String` instance.
+#[derive(Clone)]
+pub struct DuplicateString {
+    pub value: String,
+    /// the `String` instances with this value
+    pub string_object_ids: Vec<U8>,
+    /// the bytes of the strings and their arrays that would be freed by keeping only one copy
+    pub wasted_bytes: u64,
+}
+
+/// Decodes all strings and groups them by their value. The values that waste the most memory come first.
+pub fn duplicate_strings(heap_dump: &HeapDump) -> Vec<DuplicateString> {
+    heap_dump
+        .classes
+        .values()
+        .filter(|class_info| heap_dump.class_name(class_info.class_object_id) == "java/lang/String")
+        .flat_map(|class_info| heap_dump.instances(class_info.class_object_id))
+        .map(|string| string.object_id())
+        .filter_map(|string| Some((heap_dump.read_string(string)?, string)))
+        .into_group_map()
+        .into_iter()
+        .filter(|(_, strings)| strings.len() > 1)
+        .map(|(value, strings)| {
+            // strings can share their array, e.g. after deduplication by the G1 collector
+            let arrays = strings
+                .iter()
+                .filter_map(|string| heap_dump.reference_field(*string, "value"))
+                .unique()
+                .collect::<Vec<_>>();
+            let string_size = |string: &U8| heap_dump.shallow_size(*string);
+            let array_size = |array: &U8| heap_dump.shallow_size(*array);
+            let total = strings.iter().map(string_size).sum::<u64>()
+                + arrays.iter().map(array_size).sum::<u64>();
+            let one_copy =
+                strings.first().map_or(0, string_size) + arrays.first().map_or(0, array_size);
+            DuplicateString {
+                value,
+                wasted_bytes: total - one_copy,
+                string_object_ids: strings,
+            }
+        })
+        .sorted_by_key(|duplicate| Reverse(duplicate.wasted_bytes))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+    use hprof_rs::hprof_model::Value;
+
+    #[test]
+    fn strings_are_grouped_by_value_and_shared_arrays_are_counted_once() {
+        let mut builder = HeapDumpBuilder::new();
+        let string_class = builder.class("java/lang/String", 0, 0, &["value", "coder"]);
+        let mut array = |value: &str| {
+            builder.primitive_array(value.bytes().map(|byte| Value::Byte(byte as i8)).collect())
+        };
+        let first_array = array("duplicate");
+        let second_array = array("duplicate");
+        let unique_array = array("unique");
+        let strings = [first_array, second_array, first_array, unique_array]
+            .map(|array| builder.instance(string_class, vec![object(array), Value::Byte(0)]));
+        let heap_dump = builder.build();
+
+        let duplicates = duplicate_strings(&heap_dump);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].value, "duplicate");
+        let mut string_object_ids = duplicates[0].string_object_ids.clone();
+        string_object_ids.sort();
+        assert_eq!(string_object_ids, strings[..3]);
+        // two of the strings and one of the arrays could be freed
+        let string_size = heap_dump.shallow_size(strings[0]);
+        let array_size = heap_dump.shallow_size(first_array);
+        assert!(string_size > 0 && array_size > 0);
+        assert_eq!(duplicates[0].wasted_bytes, 2 * string_size + array_size);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::analysis::plugins::plugins;
+use crate::heap_dump::{EdgeOrigin, HeapDump};
+use hprof_rs::hprof_model::U8;
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// The listeners of one plugin that are registered for one event type.
+#[derive(Clone)]
+pub struct ListenerGroup {
+    pub handler_list_object_id: U8,
+    /// the event class that holds the handler list in a static field
+    pub event_class_object_id: Option<U8>,
+    pub plugin_object_id: U8,
+    /// the main class of the plugin, if the plugin object is a `JavaPlugin`
+    pub plugin_main_class_object_id: Option<U8>,
+    pub plugin_enabled: bool,
+    /// the `RegisteredListener` objects
+    pub registrations: Vec<U8>,
+    /// registrations of a listener class that is registered more than once, e.g. after a reload
+    pub redundant_registrations: usize,
+}
+
+/// Decodes all Bukkit `HandlerList`s and groups their registered listeners by event type and plugin.
+/// The groups with the most registrations come first.
+pub fn listener_groups(heap_dump: &HeapDump) -> Vec<ListenerGroup> {
+    let plugins = plugins(heap_dump)
+        .into_iter()
+        .map(|plugin| (plugin.object_id, plugin))
+        .collect::<HashMap<_, _>>();
+    let handler_list_classes = heap_dump
+        .classes
+        .values()
+        .filter(|class_info| {
+            heap_dump.is_subclass_of(class_info.class_object_id, "org/bukkit/event/HandlerList")
+        })
+        .map(|class_info| class_info.class_object_id)
+        .collect::<Vec<_>>();
+
+    handler_list_classes
+        .iter()
+        .flat_map(|class_object_id| heap_dump.instances(*class_object_id))
+        .map(|handler_list| handler_list.object_id())
+        .flat_map(|handler_list| {
+            let event_class_object_id =
+                heap_dump
+                    .referrers(handler_list)
+                    .into_iter()
+                    .find_map(|(_, edge)| match edge.origin {
+                        EdgeOrigin::StaticField(field) => Some(field.declaring_class_object_id),
+                        _ => None,
+                    });
+            registered_listeners(heap_dump, handler_list)
+                .into_iter()
+                .filter_map(|registration| {
+                    let plugin = heap_dump.reference_field(registration, "plugin")?;
+                    Some((plugin, registration))
+                })
+                .into_group_map()
+                .into_iter()
+                .map(|(plugin_object_id, registrations)| {
+                    let plugin = plugins.get(&plugin_object_id);
+                    let listener_classes = registrations
+                        .iter()
+                        .filter_map(|registration| {
+                            heap_dump.reference_field(*registration, "listener")
+                        })
+                        .filter_map(|listener| heap_dump.class_of(listener))
+                        .counts();
+                    ListenerGroup {
+                        handler_list_object_id: handler_list,
+                        event_class_object_id,
+                        plugin_object_id,
+                        plugin_main_class_object_id: plugin
+                            .map(|plugin| plugin.main_class_object_id),
+                        plugin_enabled: plugin.map(|plugin| plugin.enabled).unwrap_or(true),
+                        redundant_registrations: listener_classes
+                            .values()
+                            .map(|count| count - 1)
+                            .sum(),
+                        registrations,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .sorted_by_key(|group| Reverse(group.registrations.len()))
+        .collect()
+}
+
+/// The `RegisteredListener`s of a handler list. They are read from the per-priority slots,
+/// as the baked `handlers` array is only rebuilt when an event is fired.
+fn registered_listeners(heap_dump: &HeapDump, handler_list: U8) -> Vec<U8> {
+    let slots = heap_dump
+        .reference_field(handler_list, "handlerslots")
+        .and_then(|enum_map| heap_dump.reference_field(enum_map, "vals"))
+        .and_then(|values| heap_dump.array_elements(values));
+    match slots {
+        Some(slots) => slots
+            .iter()
+            .filter(|slot| **slot != 0)
+            .filter_map(|slot| heap_dump.read_list(*slot))
+            .flatten()
+            .collect(),
+        None => heap_dump
+            .reference_field(handler_list, "handlers")
+            .and_then(|handlers| heap_dump.array_elements(handlers))
+            .map(|handlers| {
+                handlers
+                    .iter()
+                    .copied()
+                    .filter(|handler| *handler != 0)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+    use hprof_rs::hprof_model::Value;
+
+    #[test]
+    fn listeners_are_read_from_the_slots_and_grouped_by_plugin() {
+        let mut builder = HeapDumpBuilder::new();
+        let java_plugin_class =
+            builder.class("org/bukkit/plugin/java/JavaPlugin", 0, 0, &["isEnabled"]);
+        let plugin_class = builder.class("example/ExamplePlugin", java_plugin_class, 0, &[]);
+        let handler_list_class = builder.class(
+            "org/bukkit/event/HandlerList",
+            0,
+            0,
+            &["handlers", "handlerslots"],
+        );
+        let enum_map_class = builder.class("java/util/EnumMap", 0, 0, &["vals"]);
+        let array_class = builder.class("[Ljava/lang/Object;", 0, 0, &[]);
+        let registration_class = builder.class(
+            "org/bukkit/plugin/RegisteredListener",
+            0,
+            0,
+            &["listener", "plugin"],
+        );
+        let listener_class = builder.class("example/ExampleListener", 0, 0, &[]);
+        let event_class = builder.class("example/ExampleEvent", 0, 0, &[]);
+
+        let plugin = builder.instance(plugin_class, vec![Value::Boolean(false)]);
+        let register = |builder: &mut HeapDumpBuilder| {
+            let listener = builder.instance(listener_class, vec![]);
+            builder.instance(registration_class, vec![object(listener), object(plugin)])
+        };
+        let first = register(&mut builder);
+        let second = register(&mut builder);
+        // a listener that was only baked into the stale handlers array
+        let baked = register(&mut builder);
+        let normal = builder.list(vec![first]);
+        let high = builder.list(vec![second]);
+        let slots = builder.object_array(array_class, vec![0, normal, 0, high]);
+        let handler_slots = builder.instance(enum_map_class, vec![object(slots)]);
+        let handlers = builder.object_array(array_class, vec![baked]);
+        let handler_list = builder.instance(
+            handler_list_class,
+            vec![object(handlers), object(handler_slots)],
+        );
+        builder.static_field(event_class, "handlers", object(handler_list));
+        let heap_dump = builder.build();
+
+        let groups = listener_groups(&heap_dump);
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.handler_list_object_id, handler_list);
+        assert_eq!(group.event_class_object_id, Some(event_class));
+        assert_eq!(group.plugin_object_id, plugin);
+        assert_eq!(group.plugin_main_class_object_id, Some(plugin_class));
+        assert!(!group.plugin_enabled);
+        assert_eq!(group.registrations, [first, second]);
+        // both registrations are of the same listener class
+        assert_eq!(group.redundant_registrations, 1);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::heap_dump::HeapDump;
+use hprof_rs::hprof_model::U8;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// A package with the summed instance counts and sizes of all classes in it
+/// and its subpackages.
+pub struct Package {
+    /// the full name of the package, e.g. `org/bukkit`, or an empty string for the default package
+    pub name: String,
+    /// subpackages, largest shallow size first
+    pub packages: Vec<Rc<Package>>,
+    /// classes directly in this package, largest shallow size first
+    pub classes: Vec<(U8, usize, u64)>,
+    pub instance_count: usize,
+    pub shallow_size: u64,
+}
+
+impl Package {
+    /// The last segment of the package name, e.g. `bukkit` for `org/bukkit`.
+    pub fn simple_name(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or(&self.name)
+    }
+}
+
+#[derive(Default)]
+struct PackageBuilder {
+    packages: BTreeMap<String, PackageBuilder>,
+    classes: Vec<(U8, usize, u64)>,
+    instance_count: usize,
+    shallow_size: u64,
+}
+
+impl PackageBuilder {
+    fn build(self, name: String) -> Package {
+        let mut packages = self
+            .packages
+            .into_iter()
+            .map(|(simple_name, package)| {
+                let full_name = if name.is_empty() {
+                    simple_name
+                } else {
+                    format!("{}/{}", name, simple_name)
+                };
+                Rc::new(package.build(full_name))
+            })
+            .collect::<Vec<_>>();
+        packages.sort_by_key(|package| Reverse(package.shallow_size));
+        let mut classes = self.classes;
+        classes.sort_by_key(|(_, _, shallow_size)| Reverse(*shallow_size));
+        Package {
+            name,
+            packages,
+            classes,
+            instance_count: self.instance_count,
+            shallow_size: self.shallow_size,
+        }
+    }
+}
+
+/// Groups all classes by their package. The returned package is the default package,
+/// every other package is nested in it.
+pub fn package_tree(heap_dump: &HeapDump) -> Package {
+    let mut root = PackageBuilder::default();
+    for class_info in heap_dump.classes.values() {
+        let instances = heap_dump.instances(class_info.class_object_id);
+        let instance_count = instances.len();
+        let shallow_size = instances
+            .iter()
+            .map(|instance| instance.shallow_size())
+            .sum::<u64>();
+
+        let mut package = &mut root;
+        package.instance_count += instance_count;
+        package.shallow_size += shallow_size;
+        for segment in package_segments(heap_dump.class_name(class_info.class_object_id)) {
+            package = package.packages.entry(segment.to_string()).or_default();
+            package.instance_count += instance_count;
+            package.shallow_size += shallow_size;
+        }
+        package
+            .classes
+            .push((class_info.class_object_id, instance_count, shallow_size));
+    }
+    root.build(String::new())
+}
+
+/// The package segments of a class name. Array classes are grouped with their element class.
+fn package_segments(class_name: &str) -> impl Iterator<Item = &str> {
+    let element_name = class_name.trim_start_matches('[');
+    let element_name = if element_name.len() < class_name.len() {
+        // array class names use descriptors, e.g. [Ljava/lang/String;
+        element_name
+            .strip_prefix('L')
+            .and_then(|name| name.strip_suffix(';'))
+            .unwrap_or("")
+    } else {
+        element_name
+    };
+    let package = element_name
+        .rsplit_once('/')
+        .map(|(package, _)| package)
+        .unwrap_or("");
+    package.split('/').filter(|segment| !segment.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(class_name: &str) -> Vec<&str> {
+        package_segments(class_name).collect()
+    }
+
+    #[test]
+    fn package_segments_of_classes() {
+        assert_eq!(segments("java/lang/String"), ["java", "lang"]);
+        assert_eq!(segments("java/util/HashMap$Node"), ["java", "util"]);
+        assert!(segments("Main").is_empty());
+    }
+
+    #[test]
+    fn package_segments_of_arrays() {
+        assert_eq!(segments("[Ljava/lang/String;"), ["java", "lang"]);
+        assert_eq!(segments("[[Lorg/bukkit/Location;"), ["org", "bukkit"]);
+        // primitive arrays are in the default package
+        assert!(segments("[I").is_empty());
+        assert!(segments("[[B").is_empty());
+    }
+}
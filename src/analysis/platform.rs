@@ -0,0 +1,277 @@
+use crate::heap_dump::HeapDump;
+
+/// A server software, plugins are the mods of mod loaders.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Platform {
+    Bukkit,
+    Paper,
+    Folia,
+    Fabric,
+    Forge,
+}
+
+impl Platform {
+    pub fn label(self) -> &'static str {
+        match self {
+            Platform::Bukkit => "CraftBukkit / Spigot",
+            Platform::Paper => "Paper",
+            Platform::Folia => "Folia",
+            Platform::Fabric => "Fabric",
+            Platform::Forge => "Forge",
+        }
+    }
+}
+
+/// The class names that differ between platforms. `*` matches any number of characters,
+/// e.g. the version in the package of CraftBukkit classes like `org/bukkit/craftbukkit/v1_20_R3`.
+pub struct PlatformProfile {
+    pub platform: Platform,
+    /// classes that only exist on this platform
+    markers: &'static [&'static str],
+    /// the base classes of plugin instances or mod containers
+    pub plugin_classes: &'static [&'static str],
+    /// the classes of player objects, the wrappers of the plugin API if there are any
+    pub player_classes: &'static [&'static str],
+    /// the field of a player object that refers to the server's player entity,
+    /// `None` if the player objects are the entities themselves
+    pub player_handle_field: Option<&'static str>,
+    /// the classes of the server's list of online players
+    pub player_list_classes: &'static [&'static str],
+    /// the classes of the loaders that load the server itself, see
+    /// [crate::analysis::detectors::DetectorSettings]
+    pub trusted_loaders: &'static [&'static str],
+}
+
+const BUKKIT_PLUGIN_CLASSES: &[&str] = &["org/bukkit/plugin/java/JavaPlugin"];
+const CRAFT_PLAYER_CLASSES: &[&str] = &[
+    "org/bukkit/craftbukkit/entity/CraftPlayer",
+    "org/bukkit/craftbukkit/v*/entity/CraftPlayer",
+];
+/// by their Mojang name and, for Fabric, their intermediary name
+const PLAYER_CLASSES: &[&str] = &[
+    "net/minecraft/server/level/ServerPlayer",
+    "net/minecraft/class_3222",
+];
+const PLAYER_LIST_CLASSES: &[&str] = &[
+    "net/minecraft/server/players/PlayerList",
+    "net/minecraft/class_3324",
+];
+const BUKKIT_TRUSTED_LOADERS: &[&str] = &[
+    "jdk/internal/loader/ClassLoaders$AppClassLoader",
+    "sun/misc/Launcher$AppClassLoader",
+];
+
+/// All known platforms, the most specific ones first as e.g. Folia also contains Paper's classes.
+pub const PROFILES: &[PlatformProfile] = &[
+    PlatformProfile {
+        platform: Platform::Folia,
+        markers: &["io/papermc/paper/threadedregions/RegionizedServer"],
+        plugin_classes: BUKKIT_PLUGIN_CLASSES,
+        player_classes: CRAFT_PLAYER_CLASSES,
+        player_handle_field: Some("entity"),
+        player_list_classes: PLAYER_LIST_CLASSES,
+        trusted_loaders: BUKKIT_TRUSTED_LOADERS,
+    },
+    PlatformProfile {
+        platform: Platform::Paper,
+        markers: &[
+            "io/papermc/paper/plugin/manager/PaperPluginInstanceManager",
+            "com/destroystokyo/paper/PaperConfig",
+        ],
+        plugin_classes: BUKKIT_PLUGIN_CLASSES,
+        player_classes: CRAFT_PLAYER_CLASSES,
+        player_handle_field: Some("entity"),
+        player_list_classes: PLAYER_LIST_CLASSES,
+        trusted_loaders: BUKKIT_TRUSTED_LOADERS,
+    },
+    PlatformProfile {
+        platform: Platform::Fabric,
+        markers: &["net/fabricmc/loader/api/FabricLoader"],
+        plugin_classes: &["net/fabricmc/loader/impl/ModContainerImpl"],
+        player_classes: PLAYER_CLASSES,
+        player_handle_field: None,
+        player_list_classes: PLAYER_LIST_CLASSES,
+        trusted_loaders: &[
+            "jdk/internal/loader/ClassLoaders$AppClassLoader",
+            // Knot loads the game as well as all mods, so only leaks outside of them are found
+            "net/fabricmc/loader/impl/launch/knot/KnotClassLoader",
+        ],
+    },
+    PlatformProfile {
+        platform: Platform::Forge,
+        markers: &[
+            "net/minecraftforge/fml/ModContainer",
+            "net/neoforged/fml/ModContainer",
+        ],
+        plugin_classes: &[
+            "net/minecraftforge/fml/ModContainer",
+            "net/neoforged/fml/ModContainer",
+        ],
+        player_classes: PLAYER_CLASSES,
+        player_handle_field: None,
+        player_list_classes: PLAYER_LIST_CLASSES,
+        trusted_loaders: &[
+            "jdk/internal/loader/ClassLoaders$AppClassLoader",
+            // ModLauncher loads the game as well as all mods, so only leaks outside of them are found
+            "cpw/mods/modlauncher/TransformingClassLoader",
+            "cpw/mods/cl/ModuleClassLoader",
+        ],
+    },
+    PlatformProfile {
+        platform: Platform::Bukkit,
+        markers: &["org/bukkit/Bukkit"],
+        plugin_classes: BUKKIT_PLUGIN_CLASSES,
+        player_classes: CRAFT_PLAYER_CLASSES,
+        player_handle_field: Some("entity"),
+        player_list_classes: PLAYER_LIST_CLASSES,
+        trusted_loaders: BUKKIT_TRUSTED_LOADERS,
+    },
+];
+
+impl PlatformProfile {
+    pub fn is_plugin_class(&self, class_name: &str) -> bool {
+        matches_any(self.plugin_classes, class_name)
+    }
+
+    pub fn is_player_class(&self, class_name: &str) -> bool {
+        matches_any(self.player_classes, class_name)
+    }
+
+    pub fn is_player_list_class(&self, class_name: &str) -> bool {
+        matches_any(self.player_list_classes, class_name)
+    }
+}
+
+/// Detects the platform from the classes in the heap dump, falling back to Bukkit.
+/// Use [HeapDump::platform], which only does this once.
+pub fn detect_platform(heap_dump: &HeapDump) -> &'static PlatformProfile {
+    PROFILES
+        .iter()
+        .find(|profile| {
+            heap_dump.classes.values().any(|class_info| {
+                profile
+                    .markers
+                    .contains(&heap_dump.class_name(class_info.class_object_id))
+            })
+        })
+        .unwrap_or(&PROFILES[PROFILES.len() - 1])
+}
+
+fn matches_any(patterns: &[&str], name: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| matches_pattern(pattern, name))
+}
+
+/// Whether a class is the CraftBukkit class with the given name relative to the
+/// `org/bukkit/craftbukkit` package, which is versioned on Spigot, e.g. `org/bukkit/craftbukkit/v1_20_R3`.
+pub fn is_craftbukkit_class(class_name: &str, name: &str) -> bool {
+    let Some(rest) = class_name.strip_prefix("org/bukkit/craftbukkit/") else {
+        return false;
+    };
+    let rest = match rest.split_once('/') {
+        Some((package, rest)) if package.starts_with('v') && package.contains("_R") => rest,
+        _ => rest,
+    };
+    rest == name
+}
+
+/// Whether a name matches a pattern in which `*` stands for any number of characters.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let Some(last) = parts.pop() else {
+        // no wildcard at all
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::HeapDumpBuilder;
+
+    #[test]
+    fn patterns_match_names() {
+        assert!(matches_pattern(
+            "java/net/URLClassLoader",
+            "java/net/URLClassLoader"
+        ));
+        assert!(!matches_pattern(
+            "java/net/URLClassLoader",
+            "java/net/URLClassLoader2"
+        ));
+        assert!(matches_pattern("java/net/*", "java/net/URLClassLoader"));
+        assert!(matches_pattern("*Loader", "java/net/URLClassLoader"));
+        assert!(matches_pattern(
+            "org/bukkit/craftbukkit/v*/entity/CraftPlayer",
+            "org/bukkit/craftbukkit/v1_20_R3/entity/CraftPlayer"
+        ));
+        assert!(matches_pattern("a*b*c", "a_b_b_c"));
+        assert!(!matches_pattern("a*b*c", "a_c_b"));
+        // the parts around a wildcard must not overlap
+        assert!(!matches_pattern("ab*bc", "abc"));
+        assert!(matches_pattern("*", ""));
+    }
+
+    #[test]
+    fn craftbukkit_classes_are_found_with_and_without_version() {
+        assert!(is_craftbukkit_class(
+            "org/bukkit/craftbukkit/entity/CraftPlayer",
+            "entity/CraftPlayer"
+        ));
+        assert!(is_craftbukkit_class(
+            "org/bukkit/craftbukkit/v1_20_R3/entity/CraftPlayer",
+            "entity/CraftPlayer"
+        ));
+        assert!(is_craftbukkit_class(
+            "org/bukkit/craftbukkit/v1_8_R1/CraftServer",
+            "CraftServer"
+        ));
+        assert!(!is_craftbukkit_class(
+            "org/bukkit/craftbukkit/v1_20_R3/entity/CraftPlayer",
+            "CraftPlayer"
+        ));
+        assert!(!is_craftbukkit_class(
+            "org/bukkit/craftbukkit/entity/CraftPlayerProfile",
+            "entity/CraftPlayer"
+        ));
+        assert!(!is_craftbukkit_class(
+            "example/org/bukkit/craftbukkit/entity/CraftPlayer",
+            "entity/CraftPlayer"
+        ));
+    }
+
+    #[test]
+    fn the_most_specific_platform_is_detected() {
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("org/bukkit/Bukkit", 0, 0, &[]);
+        assert_eq!(builder.build().platform().platform, Platform::Bukkit);
+
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("org/bukkit/Bukkit", 0, 0, &[]);
+        builder.class("com/destroystokyo/paper/PaperConfig", 0, 0, &[]);
+        builder.class(
+            "io/papermc/paper/threadedregions/RegionizedServer",
+            0,
+            0,
+            &[],
+        );
+        assert_eq!(builder.build().platform().platform, Platform::Folia);
+
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("net/fabricmc/loader/api/FabricLoader", 0, 0, &[]);
+        assert_eq!(builder.build().platform().platform, Platform::Fabric);
+    }
+}
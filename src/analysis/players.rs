@@ -0,0 +1,267 @@
+use crate::analysis::platform::{is_craftbukkit_class, PlatformProfile};
+use crate::heap_dump::{super_classes, HeapDump, Reference, ReferenceFilter};
+use hprof_rs::hprof_model::{Value, U8};
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+const GAME_PROFILE_CLASS: &str = "com/mojang/authlib/GameProfile";
+/// the list of online players in the server's player list, by its Mojang and intermediary name
+const PLAYER_LIST_FIELDS: [&str; 2] = ["players", "field_14351"];
+
+/// A player object, e.g. a `CraftPlayer`, with the identity decoded from its `GameProfile`.
+#[derive(Clone)]
+pub struct Player {
+    pub object_id: U8,
+    /// the server's player entity, the player object itself on platforms without a plugin API
+    pub handle_object_id: Option<U8>,
+    pub name: Option<String>,
+    pub uuid: Option<String>,
+    /// whether the player is in the server's list of online players
+    pub online: bool,
+    pub retained_size: u64,
+}
+
+/// The classes of the player objects of the platform, see [PlatformProfile::player_classes],
+/// including their subclasses.
+pub fn player_classes(heap_dump: &HeapDump) -> Vec<U8> {
+    let profile = heap_dump.platform();
+    heap_dump
+        .classes
+        .keys()
+        .copied()
+        .filter(|class_object_id| {
+            super_classes(&heap_dump.classes, *class_object_id).any(|class_info| {
+                profile.is_player_class(heap_dump.class_name(class_info.class_object_id))
+            })
+        })
+        .sorted()
+        .collect()
+}
+
+/// The UUIDs of all `GameProfile`s, i.e. of the players that are or were on the server,
+/// including those only known from the server's user cache.
+pub fn known_player_uuids(heap_dump: &HeapDump) -> HashSet<String> {
+    heap_dump
+        .classes
+        .values()
+        .filter(|class_info| heap_dump.class_name(class_info.class_object_id) == GAME_PROFILE_CLASS)
+        .flat_map(|class_info| heap_dump.instances(class_info.class_object_id))
+        .filter_map(|profile| heap_dump.reference_field(profile.object_id(), "id"))
+        .filter_map(|uuid| heap_dump.read_uuid(uuid))
+        .collect()
+}
+
+/// Finds all player objects, offline players first, then by retained size.
+pub fn players(heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<Player> {
+    let classes = player_classes(heap_dump);
+    let profile = heap_dump.platform();
+    let online = online_handles(heap_dump, profile, &classes);
+    let mut players = classes
+        .iter()
+        .flat_map(|class_object_id| heap_dump.instances(*class_object_id))
+        .map(|instance| {
+            let object_id = instance.object_id();
+            let handle_object_id = handle(heap_dump, profile, object_id);
+            let profile = handle_object_id
+                .and_then(|handle| field_of_type(heap_dump, handle, GAME_PROFILE_CLASS));
+            Player {
+                object_id,
+                handle_object_id,
+                name: profile
+                    .and_then(|profile| heap_dump.reference_field(profile, "name"))
+                    .and_then(|name| heap_dump.read_string(name)),
+                uuid: profile
+                    .and_then(|profile| heap_dump.reference_field(profile, "id"))
+                    .and_then(|uuid| heap_dump.read_uuid(uuid)),
+                online: handle_object_id.is_some_and(|handle| online.contains(&handle)),
+                retained_size: heap_dump.retained_size(object_id, filter).unwrap_or(0),
+            }
+        })
+        .collect::<Vec<_>>();
+    players.sort_by_key(|player| (player.online, Reverse(player.retained_size)));
+    players
+}
+
+/// The player entity of a player object.
+fn handle(heap_dump: &HeapDump, profile: &PlatformProfile, object_id: U8) -> Option<U8> {
+    match profile.player_handle_field {
+        Some(field) => heap_dump.reference_field(object_id, field),
+        None => Some(object_id),
+    }
+}
+
+/// The player entities of the online players. They are read from the `playerView` of the
+/// `CraftServer` and from the `players` list of the server's player list.
+fn online_handles(
+    heap_dump: &HeapDump,
+    profile: &PlatformProfile,
+    player_classes: &[U8],
+) -> HashSet<U8> {
+    let mut handles = HashSet::new();
+    let servers = heap_dump.classes.values().filter(|class_info| {
+        is_craftbukkit_class(
+            heap_dump.class_name(class_info.class_object_id),
+            "CraftServer",
+        )
+    });
+    for server in servers.flat_map(|class_info| heap_dump.instances(class_info.class_object_id)) {
+        let mut list = heap_dump.reference_field(server.object_id(), "playerView");
+        // the view is an unmodifiable list that transforms the entities of the player list
+        while let Some(current) = list {
+            let inner = match heap_dump.type_name(current).as_str() {
+                "java/util/Collections$UnmodifiableList"
+                | "java/util/Collections$UnmodifiableRandomAccessList" => "list",
+                "com/google/common/collect/Lists$TransformingRandomAccessList"
+                | "com/google/common/collect/Lists$TransformingSequentialList" => "fromList",
+                _ => break,
+            };
+            list = heap_dump.reference_field(current, inner);
+        }
+        let elements = list
+            .and_then(|list| heap_dump.read_list(list))
+            .unwrap_or_default();
+        handles.extend(elements.into_iter().filter_map(|element| {
+            let is_player = heap_dump
+                .class_of(element)
+                .is_some_and(|class_object_id| player_classes.contains(&class_object_id));
+            if is_player {
+                handle(heap_dump, profile, element)
+            } else {
+                Some(element)
+            }
+        }));
+    }
+
+    let player_lists = heap_dump.classes.values().filter(|class_info| {
+        profile.is_player_list_class(heap_dump.class_name(class_info.class_object_id))
+    });
+    for player_list in
+        player_lists.flat_map(|class_info| heap_dump.instances(class_info.class_object_id))
+    {
+        let players = PLAYER_LIST_FIELDS
+            .iter()
+            .find_map(|field| heap_dump.reference_field(player_list.object_id(), field))
+            .and_then(|players| heap_dump.read_list(players));
+        handles.extend(players.unwrap_or_default());
+    }
+    handles
+}
+
+/// The first object referenced by a field of an object that is an instance of the given class.
+/// Used where field names are obfuscated, but the type of the field is known.
+fn field_of_type(heap_dump: &HeapDump, object_id: U8, class_name: &str) -> Option<U8> {
+    let Some(Reference::Instance(instance)) = heap_dump.objects.get(&object_id).map(|r| &**r)
+    else {
+        return None;
+    };
+    instance.fields.iter().find_map(|value| match value {
+        Value::Object { object_id } if *object_id != 0 => heap_dump
+            .class_of(*object_id)
+            .filter(|class_object_id| heap_dump.class_name(*class_object_id) == class_name)
+            .map(|_| *object_id),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn players_of_all_player_classes_are_classified_by_the_player_list() {
+        let mut builder = HeapDumpBuilder::new();
+        let craft_player = builder.class(
+            "org/bukkit/craftbukkit/entity/CraftPlayer",
+            0,
+            0,
+            &["entity"],
+        );
+        let versioned_player = builder.class(
+            "org/bukkit/craftbukkit/v1_20_R3/entity/CraftPlayer",
+            0,
+            0,
+            &["entity"],
+        );
+        let fake_player = builder.class("example/FakePlayer", craft_player, 0, &[]);
+        let server_player = builder.class(
+            "net/minecraft/server/level/ServerPlayer",
+            0,
+            0,
+            &["gameProfile"],
+        );
+        let profile_class = builder.class(GAME_PROFILE_CLASS, 0, 0, &["id", "name"]);
+        let player_list_class = builder.class(
+            "net/minecraft/server/players/PlayerList",
+            0,
+            0,
+            &["players", "recentlyLeft"],
+        );
+
+        let player = |builder: &mut HeapDumpBuilder, class_object_id: U8, name: &str| {
+            let name = builder.string(name);
+            let profile = builder.instance(profile_class, vec![object(0), object(name)]);
+            let handle = builder.instance(server_player, vec![object(profile)]);
+            let player = builder.instance(class_object_id, vec![object(handle)]);
+            builder.root(player);
+            (player, handle)
+        };
+        let (alice, alice_handle) = player(&mut builder, craft_player, "Alice");
+        let (bob, _) = player(&mut builder, versioned_player, "Bob");
+        let (carol, carol_handle) = player(&mut builder, fake_player, "Carol");
+        // only the players field is the list of online players
+        let online = builder.list(vec![alice_handle, carol_handle]);
+        let (dave, dave_handle) = player(&mut builder, craft_player, "Dave");
+        let left = builder.list(vec![dave_handle]);
+        let player_list = builder.instance(player_list_class, vec![object(online), object(left)]);
+        builder.root(player_list);
+        let heap_dump = builder.build();
+
+        let mut players = players(&heap_dump, ReferenceFilter::All)
+            .into_iter()
+            .map(|player| (player.object_id, player.name.unwrap(), player.online))
+            .collect::<Vec<_>>();
+        players.sort();
+        assert_eq!(
+            players,
+            [
+                (alice, "Alice".to_string(), true),
+                (bob, "Bob".to_string(), false),
+                (carol, "Carol".to_string(), true),
+                (dave, "Dave".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn online_players_are_read_from_the_intermediary_field_on_fabric() {
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("net/fabricmc/loader/api/FabricLoader", 0, 0, &[]);
+        let server_player = builder.class("net/minecraft/class_3222", 0, 0, &[]);
+        let player_list_class = builder.class(
+            "net/minecraft/class_3324",
+            0,
+            0,
+            &["field_14350", "field_14351"],
+        );
+        let online = builder.instance(server_player, vec![]);
+        let offline = builder.instance(server_player, vec![]);
+        builder.root(offline);
+        let online_list = builder.list(vec![online]);
+        let others = builder.list(vec![offline]);
+        let player_list =
+            builder.instance(player_list_class, vec![object(others), object(online_list)]);
+        builder.root(player_list);
+        let heap_dump = builder.build();
+
+        let players = players(&heap_dump, ReferenceFilter::All);
+        assert_eq!(players.len(), 2);
+        // offline players come first
+        assert_eq!(players[0].object_id, offline);
+        assert!(!players[0].online);
+        assert_eq!(players[1].object_id, online);
+        assert_eq!(players[1].handle_object_id, Some(online));
+        assert!(players[1].online);
+    }
+}
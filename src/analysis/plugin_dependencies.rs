@@ -0,0 +1,235 @@
+use crate::analysis::class_loaders::owning_loader;
+use crate::analysis::plugins::{exclusive_loaders, plugins, Plugin};
+use crate::heap_dump::HeapDump;
+use hprof_rs::hprof_model::U8;
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// How one plugin depends on another.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DependencyKind {
+    Depend,
+    SoftDepend,
+    /// the plugin wants to be loaded before the other one
+    LoadBefore,
+}
+
+/// A dependency declared in a plugin's description.
+/// Plugins are identified by their [Plugin::object_id], as mods may share a main class.
+#[derive(Clone, PartialEq)]
+pub struct DeclaredDependency {
+    pub from: U8,
+    pub to: U8,
+    pub kind: DependencyKind,
+}
+
+/// All references from objects or classes of one plugin to those of another plugin.
+#[derive(Clone, PartialEq)]
+pub struct CrossReference {
+    pub from: U8,
+    pub to: U8,
+    pub count: usize,
+    /// whether `from` declares a dependency on `to`, undeclared references often cause leaks on reloads
+    pub declared: bool,
+    /// one of the references, as the referring and the referenced object
+    pub example: (U8, U8),
+}
+
+pub struct PluginGraph {
+    pub plugins: Vec<Plugin>,
+    pub dependencies: Vec<DeclaredDependency>,
+    /// ordered by the number of references, undeclared ones first
+    pub references: Vec<CrossReference>,
+}
+
+/// Builds the graph of declared dependencies between plugins together with the references
+/// that actually exist between their loaders in the object graph.
+pub fn plugin_graph(heap_dump: &HeapDump) -> PluginGraph {
+    let plugins = plugins(heap_dump);
+    let by_name = plugins
+        .iter()
+        .filter_map(|plugin| {
+            let name = plugin.description.name.as_deref()?;
+            Some((name, plugin.object_id))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let dependencies = plugins
+        .iter()
+        .flat_map(|plugin| {
+            let description = &plugin.description;
+            let declared = description
+                .depend
+                .iter()
+                .map(|name| (name, DependencyKind::Depend))
+                .chain(
+                    description
+                        .softdepend
+                        .iter()
+                        .map(|name| (name, DependencyKind::SoftDepend)),
+                )
+                .chain(
+                    description
+                        .load_before
+                        .iter()
+                        .map(|name| (name, DependencyKind::LoadBefore)),
+                );
+            let by_name = &by_name;
+            declared.filter_map(move |(name, kind)| {
+                by_name.get(name.as_str()).map(|to| DeclaredDependency {
+                    from: plugin.object_id,
+                    to: *to,
+                    kind,
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let by_loader = exclusive_loaders(&plugins);
+    let plugin_of = |object_id: U8| {
+        owning_loader(heap_dump, object_id)
+            .and_then(|loader| by_loader.get(&loader))
+            .map(|plugin| plugin.object_id)
+    };
+    let mut references: HashMap<(U8, U8), CrossReference> = HashMap::new();
+    for (referrer, referenced, _) in heap_dump.object_graph.all_edges() {
+        let (Some(from), Some(to)) = (plugin_of(referrer), plugin_of(referenced)) else {
+            continue;
+        };
+        if from == to {
+            continue;
+        }
+        references
+            .entry((from, to))
+            .or_insert_with(|| CrossReference {
+                from,
+                to,
+                count: 0,
+                declared: dependencies.iter().any(|dependency| {
+                    dependency.from == from
+                        && dependency.to == to
+                        && dependency.kind != DependencyKind::LoadBefore
+                }),
+                example: (referrer, referenced),
+            })
+            .count += 1;
+    }
+    let references = references
+        .into_values()
+        .sorted_by_key(|reference| (reference.declared, Reverse(reference.count)))
+        .collect();
+
+    PluginGraph {
+        plugins,
+        dependencies,
+        references,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn declared_dependencies_and_cross_references_are_found() {
+        let mut builder = HeapDumpBuilder::new();
+        let java_plugin =
+            builder.class("org/bukkit/plugin/java/JavaPlugin", 0, 0, &["description"]);
+        let description_class = builder.class(
+            "org/bukkit/plugin/PluginDescriptionFile",
+            0,
+            0,
+            &["name", "depend", "softDepend", "loadBefore"],
+        );
+        let loader_class = builder.class("org/bukkit/plugin/java/PluginClassLoader", 0, 0, &[]);
+        let add_plugin = |builder: &mut HeapDumpBuilder,
+                          loader: U8,
+                          name: &str,
+                          depend: &[&str],
+                          load_before: &[&str]| {
+            let name_string = builder.string(name);
+            let mut names = |names: &[&str]| {
+                let strings = names.iter().map(|name| builder.string(name)).collect();
+                builder.list(strings)
+            };
+            let depend = names(depend);
+            let softdepend = names(&[]);
+            let load_before = names(load_before);
+            let description = builder.instance(
+                description_class,
+                vec![
+                    object(name_string),
+                    object(depend),
+                    object(softdepend),
+                    object(load_before),
+                ],
+            );
+            let main_class = builder.class(
+                &format!("example/{}", name),
+                java_plugin,
+                loader,
+                &["state"],
+            );
+            let plugin = builder.instance(main_class, vec![object(0), object(description)]);
+            builder.root(plugin);
+            (plugin, main_class)
+        };
+        let loaders = [(); 3].map(|_| builder.instance(loader_class, vec![]));
+        let (a, _) = add_plugin(&mut builder, loaders[0], "A", &["B", "Missing"], &["C"]);
+        let (b, _) = add_plugin(&mut builder, loaders[1], "B", &[], &[]);
+        let (c, _) = add_plugin(&mut builder, loaders[2], "C", &[], &[]);
+        // two plugins sharing a loader cannot be told apart
+        let shared_loader = builder.instance(loader_class, vec![]);
+        let (d, _) = add_plugin(&mut builder, shared_loader, "D", &[], &[]);
+        add_plugin(&mut builder, shared_loader, "E", &[], &[]);
+
+        let b_class = builder.class("example/b/Holder", 0, loaders[1], &["target", "next"]);
+        let c_class = builder.class("example/c/Holder", 0, loaders[2], &["target", "next"]);
+        let holder = |builder: &mut HeapDumpBuilder, class, target| {
+            builder.instance(class, vec![object(target), object(0)])
+        };
+        let b_holder = holder(&mut builder, b_class, c);
+        let second_b_holder = holder(&mut builder, b_class, c);
+        builder.set_field(a, 0, object(b));
+        builder.set_field(b, 0, object(b_holder));
+        builder.set_field(b_holder, 1, object(second_b_holder));
+        builder.set_field(d, 0, object(a));
+        let c_holder = holder(&mut builder, c_class, a);
+        builder.set_field(c, 0, object(c_holder));
+        let heap_dump = builder.build();
+
+        let graph = plugin_graph(&heap_dump);
+        assert_eq!(graph.plugins.len(), 5);
+        let dependencies = graph
+            .dependencies
+            .iter()
+            .map(|dependency| (dependency.from, dependency.to, dependency.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dependencies,
+            [
+                (a, b, DependencyKind::Depend),
+                (a, c, DependencyKind::LoadBefore)
+            ]
+        );
+        let references = graph
+            .references
+            .iter()
+            .map(|reference| {
+                (
+                    reference.from,
+                    reference.to,
+                    reference.count,
+                    reference.declared,
+                )
+            })
+            .collect::<Vec<_>>();
+        // loading before another plugin does not allow referencing it
+        assert_eq!(
+            references,
+            [(b, c, 2, false), (c, a, 1, false), (a, b, 1, true)]
+        );
+    }
+}
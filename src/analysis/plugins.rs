@@ -0,0 +1,495 @@
+use crate::analysis::class_loaders::class_loaders;
+use crate::analysis::platform::{Platform, PlatformProfile};
+use crate::heap_dump::{AnalysisClassInfo, HeapDump, InstanceInfo, Reference, ReferenceFilter};
+use hprof_rs::hprof_model::{Value, U8};
+use itertools::Itertools;
+use mini_moka::unsync::Cache;
+use std::collections::{HashMap, HashSet};
+
+/// A loaded Bukkit plugin, i.e. an instance of a `JavaPlugin` subclass,
+/// or the container of a mod on mod loaders.
+#[derive(Clone)]
+pub struct Plugin {
+    /// the plugin instance or mod container, which identifies the plugin
+    pub object_id: U8,
+    /// for mods, the class of the container unless the container names the mod's main class
+    pub main_class_object_id: U8,
+    /// the loader that defined the main class and, usually, all other classes of the plugin,
+    /// mod loaders share one loader between all mods
+    pub class_loader_object_id: U8,
+    /// the value of `JavaPlugin.isEnabled`, plugins are assumed to be enabled if it cannot be read
+    pub enabled: bool,
+    pub description: PluginDescription,
+}
+
+/// The parts of a plugin's `PluginDescriptionFile` (its `plugin.yml`) that could be decoded.
+/// For mods, the name is the mod id and the dependencies are taken from the mod's metadata.
+#[derive(Clone, Default)]
+pub struct PluginDescription {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub authors: Vec<String>,
+    pub depend: Vec<String>,
+    pub softdepend: Vec<String>,
+    pub load_before: Vec<String>,
+}
+
+impl PluginDescription {
+    /// Decodes the description of a plugin instance or mod container.
+    fn read(heap_dump: &HeapDump, profile: &PlatformProfile, object_id: U8) -> PluginDescription {
+        match profile.platform {
+            Platform::Bukkit | Platform::Paper | Platform::Folia => {
+                PluginDescription::read_plugin(heap_dump, object_id)
+            }
+            Platform::Fabric => PluginDescription::read_fabric_mod(heap_dump, object_id),
+            Platform::Forge => PluginDescription::read_forge_mod(heap_dump, object_id),
+        }
+    }
+
+    /// Decodes the `PluginDescriptionFile` referenced by a plugin instance.
+    fn read_plugin(heap_dump: &HeapDump, plugin_object_id: U8) -> PluginDescription {
+        let Some(object_id) = heap_dump.reference_field(plugin_object_id, "description") else {
+            return PluginDescription::default();
+        };
+        let strings = |field: &str| {
+            heap_dump
+                .reference_field(object_id, field)
+                .and_then(|list| heap_dump.read_string_list(list))
+                .unwrap_or_default()
+        };
+        PluginDescription {
+            name: read_string_field(heap_dump, object_id, "name"),
+            version: read_string_field(heap_dump, object_id, "version"),
+            authors: strings("authors"),
+            depend: strings("depend"),
+            softdepend: strings("softDepend"),
+            load_before: strings("loadBefore"),
+        }
+    }
+
+    /// Decodes the `ModMetadata` of a Fabric `ModContainerImpl`.
+    fn read_fabric_mod(heap_dump: &HeapDump, container_object_id: U8) -> PluginDescription {
+        let Some(object_id) = heap_dump.reference_field(container_object_id, "info") else {
+            return PluginDescription::default();
+        };
+        let mut description = PluginDescription {
+            name: read_string_field(heap_dump, object_id, "id"),
+            // a `SemanticVersionImpl` or a `StringVersion`
+            version: heap_dump
+                .reference_field(object_id, "version")
+                .and_then(|version| {
+                    read_string_field(heap_dump, version, "friendlyName")
+                        .or_else(|| read_string_field(heap_dump, version, "version"))
+                }),
+            authors: read_list_field(heap_dump, object_id, "authors")
+                .into_iter()
+                .filter_map(|person| read_string_field(heap_dump, person, "name"))
+                .collect(),
+            ..PluginDescription::default()
+        };
+        for dependency in read_list_field(heap_dump, object_id, "dependencies") {
+            let Some(mod_id) = read_string_field(heap_dump, dependency, "modId") else {
+                continue;
+            };
+            match read_enum_field(heap_dump, dependency, "kind").as_deref() {
+                Some("DEPENDS") => description.depend.push(mod_id),
+                Some("RECOMMENDS" | "SUGGESTS") => description.softdepend.push(mod_id),
+                _ => {}
+            }
+        }
+        description
+    }
+
+    /// Decodes the `IModInfo` of a Forge `ModContainer`.
+    fn read_forge_mod(heap_dump: &HeapDump, container_object_id: U8) -> PluginDescription {
+        let mut description = PluginDescription {
+            name: read_string_field(heap_dump, container_object_id, "modId"),
+            ..PluginDescription::default()
+        };
+        let Some(object_id) = heap_dump.reference_field(container_object_id, "modInfo") else {
+            return description;
+        };
+        // a Maven `DefaultArtifactVersion`
+        description.version = heap_dump
+            .reference_field(object_id, "version")
+            .and_then(|version| heap_dump.reference_field(version, "comparable"))
+            .and_then(|comparable| read_string_field(heap_dump, comparable, "value"));
+        for dependency in read_list_field(heap_dump, object_id, "dependencies") {
+            let Some(mod_id) = read_string_field(heap_dump, dependency, "modId") else {
+                continue;
+            };
+            // older versions only have the flag, newer ones only the type
+            let mandatory = match heap_dump.field_value(dependency, "mandatory") {
+                Some(Value::Boolean(mandatory)) => *mandatory,
+                _ => read_enum_field(heap_dump, dependency, "type").as_deref() == Some("REQUIRED"),
+            };
+            if mandatory {
+                description.depend.push(mod_id);
+            } else {
+                description.softdepend.push(mod_id);
+            }
+        }
+        description
+    }
+}
+
+fn read_string_field(heap_dump: &HeapDump, object_id: U8, field: &str) -> Option<String> {
+    heap_dump
+        .reference_field(object_id, field)
+        .and_then(|string| heap_dump.read_string(string))
+}
+
+/// The name of the enum constant a field refers to.
+fn read_enum_field(heap_dump: &HeapDump, object_id: U8, field: &str) -> Option<String> {
+    heap_dump
+        .reference_field(object_id, field)
+        .and_then(|constant| read_string_field(heap_dump, constant, "name"))
+}
+
+fn read_list_field(heap_dump: &HeapDump, object_id: U8, field: &str) -> Vec<U8> {
+    heap_dump
+        .reference_field(object_id, field)
+        .and_then(|list| heap_dump.read_list(list))
+        .unwrap_or_default()
+}
+
+/// The class a Forge mod container names as the mod's main class, if it is known.
+fn forge_mod_class(heap_dump: &HeapDump, container_object_id: U8) -> Option<U8> {
+    heap_dump
+        .reference_field(container_object_id, "modClass")
+        // NeoForge allows several mod classes
+        .or_else(|| {
+            read_list_field(heap_dump, container_object_id, "modClasses")
+                .first()
+                .copied()
+        })
+        .filter(|class_object_id| heap_dump.classes.contains_key(class_object_id))
+}
+
+/// Finds all plugin instances, ordered by the name of their main class.
+pub fn plugins(heap_dump: &HeapDump) -> Vec<Plugin> {
+    let mut is_plugin_class_cache: Cache<U8, bool> = Cache::builder().max_capacity(512).build();
+    let profile = heap_dump.platform();
+    let classes = &heap_dump.classes;
+    let names = &heap_dump.names;
+    heap_dump
+        .objects
+        .values()
+        .filter_map(|reference| match &**reference {
+            Reference::Instance(instance) => Some(instance),
+            Reference::ObjectArray(_) => None,
+            Reference::PrimitiveArray(_) => None,
+            Reference::FakeCommonRoot => None,
+        })
+        .filter(|instance| {
+            is_plugin_class(
+                instance,
+                &mut is_plugin_class_cache,
+                profile,
+                classes,
+                names,
+            )
+        })
+        .filter_map(|instance| {
+            let main_class_object_id = match profile.platform {
+                Platform::Forge => forge_mod_class(heap_dump, instance.object_id)
+                    .unwrap_or(instance.class_object_id),
+                _ => instance.class_object_id,
+            };
+            classes.get(&main_class_object_id).map(|class_info| Plugin {
+                object_id: instance.object_id,
+                main_class_object_id,
+                class_loader_object_id: class_info.class_loader_object_id,
+                enabled: !matches!(
+                    heap_dump.field_value(instance.object_id, "isEnabled"),
+                    Some(Value::Boolean(false))
+                ),
+                description: PluginDescription::read(heap_dump, profile, instance.object_id),
+            })
+        })
+        .sorted_by_key(|plugin| {
+            (
+                heap_dump.class_name(plugin.main_class_object_id),
+                plugin.description.name.clone(),
+            )
+        })
+        .collect()
+}
+
+/// The plugins by the loader of their main class. Loaders shared by several plugins,
+/// like the one of a mod loader, are left out as their objects cannot be told apart.
+pub fn plugins_by_loader(heap_dump: &HeapDump) -> HashMap<U8, Plugin> {
+    exclusive_loaders(&plugins(heap_dump))
+        .into_iter()
+        .map(|(loader, plugin)| (loader, plugin.clone()))
+        .collect()
+}
+
+/// Like [plugins_by_loader], for plugins that were already read.
+pub fn exclusive_loaders(plugins: &[Plugin]) -> HashMap<U8, &Plugin> {
+    plugins
+        .iter()
+        .into_group_map_by(|plugin| plugin.class_loader_object_id)
+        .into_iter()
+        .filter_map(|(loader, plugins)| (plugins.len() == 1).then(|| (loader, plugins[0])))
+        .collect()
+}
+
+/// A plugin together with the memory used by instances of the classes its loader defined.
+/// The usage is zero for plugins that share their loader with others, see [plugins_by_loader].
+#[derive(Clone)]
+pub struct PluginUsage {
+    pub plugin: Plugin,
+    pub class_count: usize,
+    pub instance_count: usize,
+    pub shallow_size: u64,
+    pub retained_size: u64,
+}
+
+/// The memory usage of all plugins, ordered by the name of their main class.
+pub fn plugin_usage(heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<PluginUsage> {
+    let loaders = class_loaders(heap_dump, filter)
+        .into_iter()
+        .map(|loader| (loader.object_id, loader))
+        .collect::<HashMap<_, _>>();
+    let plugins = plugins(heap_dump);
+    let exclusive_loaders = exclusive_loaders(&plugins)
+        .into_keys()
+        .collect::<HashSet<_>>();
+    plugins
+        .into_iter()
+        .map(|plugin| {
+            let loader = loaders
+                .get(&plugin.class_loader_object_id)
+                .filter(|_| exclusive_loaders.contains(&plugin.class_loader_object_id));
+            PluginUsage {
+                class_count: loader.map_or(0, |loader| loader.defined_classes.len()),
+                instance_count: loader.map_or(0, |loader| loader.instance_count),
+                shallow_size: loader.map_or(0, |loader| loader.shallow_size),
+                retained_size: loader.map_or(0, |loader| loader.retained_size),
+                plugin,
+            }
+        })
+        .collect()
+}
+
+fn is_plugin_class(
+    instance_info: &InstanceInfo,
+    is_plugin_class_cache: &mut Cache<U8, bool>,
+    profile: &PlatformProfile,
+    classes: &HashMap<U8, AnalysisClassInfo>,
+    names: &HashMap<U8, String>,
+) -> bool {
+    let mut class_id = &instance_info.class_object_id;
+
+    if let Some(b) = is_plugin_class_cache.get(class_id) {
+        *b // cache hit
+    } else {
+        // collect all superclasses in this stack
+        let mut stack = Vec::new();
+        while let Some(class_info) = classes.get(class_id) {
+            stack.push(*class_id);
+            if is_plugin_base_class(profile, names, class_info) {
+                for x in stack {
+                    is_plugin_class_cache.insert(x, true);
+                }
+                return true;
+            }
+            class_id = &class_info.super_class_object_id;
+            if let Some(&b) = is_plugin_class_cache.get(class_id) {
+                // found superclass in cache, remember for all seen classes
+                for x in stack {
+                    is_plugin_class_cache.insert(x, b);
+                }
+                return b;
+            }
+        }
+        for x in stack {
+            is_plugin_class_cache.insert(x, false);
+        }
+        false
+    }
+}
+
+/// Whether the class is exactly one of the platform's plugin base classes, not a subclass.
+fn is_plugin_base_class(
+    profile: &PlatformProfile,
+    names: &HashMap<U8, String>,
+    class_info: &AnalysisClassInfo,
+) -> bool {
+    names
+        .get(&class_info.class_name_id)
+        .is_some_and(|name| profile.is_plugin_class(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn fabric_mods_are_told_apart_by_their_metadata() {
+        let mut builder = HeapDumpBuilder::new();
+        builder.class("net/fabricmc/loader/api/FabricLoader", 0, 0, &[]);
+        let container_class =
+            builder.class("net/fabricmc/loader/impl/ModContainerImpl", 0, 0, &["info"]);
+        let metadata_class = builder.class(
+            "net/fabricmc/loader/impl/metadata/V1ModMetadata",
+            0,
+            0,
+            &["id", "version", "authors", "dependencies"],
+        );
+        let version_class = builder.class(
+            "net/fabricmc/loader/impl/util/version/StringVersion",
+            0,
+            0,
+            &["version"],
+        );
+        let person_class = builder.class(
+            "net/fabricmc/loader/impl/metadata/SimplePerson",
+            0,
+            0,
+            &["name"],
+        );
+        let dependency_class = builder.class(
+            "net/fabricmc/loader/impl/metadata/ModDependencyImpl",
+            0,
+            0,
+            &["kind", "modId"],
+        );
+        let kind_class = builder.class(
+            "net/fabricmc/loader/api/metadata/ModDependency$Kind",
+            0,
+            0,
+            &["name"],
+        );
+        let add_mod = |builder: &mut HeapDumpBuilder,
+                       id: &str,
+                       version: &str,
+                       authors: Vec<U8>,
+                       dependencies: Vec<U8>| {
+            let id = builder.string(id);
+            let version = builder.string(version);
+            let version = builder.instance(version_class, vec![object(version)]);
+            let authors = builder.list(authors);
+            let dependencies = builder.list(dependencies);
+            let metadata = builder.instance(
+                metadata_class,
+                vec![
+                    object(id),
+                    object(version),
+                    object(authors),
+                    object(dependencies),
+                ],
+            );
+            builder.instance(container_class, vec![object(metadata)])
+        };
+        let library = add_mod(&mut builder, "library", "1.0", vec![], vec![]);
+        let name = builder.string("Alice");
+        let author = builder.instance(person_class, vec![object(name)]);
+        let dependency = |builder: &mut HeapDumpBuilder, kind: &str, mod_id: &str| {
+            let kind = builder.string(kind);
+            let kind = builder.instance(kind_class, vec![object(kind)]);
+            let mod_id = builder.string(mod_id);
+            builder.instance(dependency_class, vec![object(kind), object(mod_id)])
+        };
+        let depends = dependency(&mut builder, "DEPENDS", "library");
+        let suggests = dependency(&mut builder, "SUGGESTS", "other");
+        let example = add_mod(
+            &mut builder,
+            "example",
+            "2.0",
+            vec![author],
+            vec![depends, suggests],
+        );
+        let heap_dump = builder.build();
+
+        let plugins = plugins(&heap_dump);
+        let ids = plugins
+            .iter()
+            .map(|plugin| plugin.object_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [example, library]);
+        let description = &plugins[0].description;
+        assert_eq!(description.name.as_deref(), Some("example"));
+        assert_eq!(description.version.as_deref(), Some("2.0"));
+        assert_eq!(description.authors, ["Alice"]);
+        assert_eq!(description.depend, ["library"]);
+        assert_eq!(description.softdepend, ["other"]);
+        assert_eq!(plugins[1].description.name.as_deref(), Some("library"));
+        // both containers were loaded by the same loader
+        assert!(plugins_by_loader(&heap_dump).is_empty());
+    }
+
+    #[test]
+    fn forge_mods_use_the_mod_class_of_their_container() {
+        let mut builder = HeapDumpBuilder::new();
+        let container_class = builder.class(
+            "net/minecraftforge/fml/ModContainer",
+            0,
+            0,
+            &["modId", "modInfo"],
+        );
+        let fml_container_class = builder.class(
+            "net/minecraftforge/fml/javafmlmod/FMLModContainer",
+            container_class,
+            0,
+            &["modClass"],
+        );
+        let info_class = builder.class(
+            "net/minecraftforge/fml/loading/moddiscovery/ModInfo",
+            0,
+            0,
+            &["version", "dependencies"],
+        );
+        let version_class = builder.class(
+            "org/apache/maven/artifact/versioning/DefaultArtifactVersion",
+            0,
+            0,
+            &["comparable"],
+        );
+        let comparable_class = builder.class(
+            "org/apache/maven/artifact/versioning/ComparableVersion",
+            0,
+            0,
+            &["value"],
+        );
+        let dependency_class = builder.class(
+            "net/minecraftforge/fml/loading/moddiscovery/ModInfo$ModVersion",
+            0,
+            0,
+            &["modId", "mandatory"],
+        );
+        let loader_class = builder.class("cpw/mods/modlauncher/TransformingClassLoader", 0, 0, &[]);
+        let loader = builder.instance(loader_class, vec![]);
+        let mod_class = builder.class("example/ExampleMod", 0, loader, &[]);
+
+        let value = builder.string("1.2.3");
+        let comparable = builder.instance(comparable_class, vec![object(value)]);
+        let version = builder.instance(version_class, vec![object(comparable)]);
+        let forge = builder.string("forge");
+        let required =
+            builder.instance(dependency_class, vec![object(forge), Value::Boolean(true)]);
+        let jei = builder.string("jei");
+        let optional = builder.instance(dependency_class, vec![object(jei), Value::Boolean(false)]);
+        let dependencies = builder.list(vec![required, optional]);
+        let info = builder.instance(info_class, vec![object(version), object(dependencies)]);
+        let mod_id = builder.string("example");
+        let container = builder.instance(
+            fml_container_class,
+            vec![object(mod_class), object(mod_id), object(info)],
+        );
+        let heap_dump = builder.build();
+
+        let plugins = plugins(&heap_dump);
+        assert_eq!(plugins.len(), 1);
+        let plugin = &plugins[0];
+        assert_eq!(plugin.object_id, container);
+        assert_eq!(plugin.main_class_object_id, mod_class);
+        assert_eq!(plugin.class_loader_object_id, loader);
+        assert_eq!(plugin.description.name.as_deref(), Some("example"));
+        assert_eq!(plugin.description.version.as_deref(), Some("1.2.3"));
+        assert_eq!(plugin.description.depend, ["forge"]);
+        assert_eq!(plugin.description.softdepend, ["jei"]);
+    }
+}
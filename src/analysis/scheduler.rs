@@ -0,0 +1,228 @@
+use crate::analysis::platform::is_craftbukkit_class;
+use crate::analysis::plugins::plugins;
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use hprof_rs::hprof_model::{Value, U8};
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+/// A task of the Bukkit scheduler.
+#[derive(Clone)]
+pub struct ScheduledTask {
+    /// the `CraftTask`
+    pub object_id: U8,
+    /// the `Runnable` or `Consumer` the plugin scheduled, if any
+    pub runnable_object_id: Option<U8>,
+    /// the number of ticks between two runs, or a negative value for tasks that only run once
+    /// or were cancelled
+    pub period: Option<i64>,
+    /// the memory kept alive by the runnable, i.e. the state captured by the task
+    pub retained_size: u64,
+}
+
+/// The tasks of one plugin that run the same class.
+#[derive(Clone)]
+pub struct TaskGroup {
+    pub plugin_object_id: Option<U8>,
+    pub plugin_main_class_object_id: Option<U8>,
+    /// the class of the runnables, or of the tasks themselves if they have no runnable
+    pub task_class_object_id: Option<U8>,
+    pub tasks: Vec<ScheduledTask>,
+    pub retained_size: u64,
+}
+
+/// Reads the pending and running tasks of all `CraftScheduler`s and groups them by plugin
+/// and task class. The groups with the most tasks come first.
+pub fn scheduled_tasks(heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<TaskGroup> {
+    let plugins = plugins(heap_dump)
+        .into_iter()
+        .map(|plugin| (plugin.object_id, plugin.main_class_object_id))
+        .collect::<HashMap<_, _>>();
+
+    heap_dump
+        .classes
+        .values()
+        .filter(|class_info| {
+            is_craftbukkit_class(
+                heap_dump.class_name(class_info.class_object_id),
+                "scheduler/CraftScheduler",
+            )
+        })
+        .flat_map(|class_info| heap_dump.instances(class_info.class_object_id))
+        .flat_map(|scheduler| scheduler_tasks(heap_dump, scheduler.object_id()))
+        .unique()
+        .map(|task| {
+            let runnable_object_id = heap_dump
+                .reference_field(task, "rTask")
+                .or_else(|| heap_dump.reference_field(task, "cTask"));
+            let period = match heap_dump.field_value(task, "period") {
+                Some(Value::Long(period)) => Some(*period),
+                _ => None,
+            };
+            let retained_size = heap_dump
+                .retained_size(runnable_object_id.unwrap_or(task), filter)
+                .unwrap_or(0);
+            let plugin_object_id = heap_dump.reference_field(task, "plugin");
+            let task_class_object_id = heap_dump.class_of(runnable_object_id.unwrap_or(task));
+            let task = ScheduledTask {
+                object_id: task,
+                runnable_object_id,
+                period,
+                retained_size,
+            };
+            ((plugin_object_id, task_class_object_id), task)
+        })
+        .into_group_map()
+        .into_iter()
+        .map(
+            |((plugin_object_id, task_class_object_id), tasks)| TaskGroup {
+                plugin_object_id,
+                plugin_main_class_object_id: plugin_object_id
+                    .and_then(|plugin| plugins.get(&plugin).copied()),
+                task_class_object_id,
+                retained_size: tasks.iter().map(|task| task.retained_size).sum(),
+                tasks,
+            },
+        )
+        .sorted_by_key(|group| (Reverse(group.tasks.len()), Reverse(group.retained_size)))
+        .collect()
+}
+
+/// Describes the period of a `CraftTask`, negative periods are special states.
+pub fn describe_period(period: i64) -> String {
+    match period {
+        -1 => "once".to_string(),
+        -2 => "cancelled".to_string(),
+        -3 | -4 => "future".to_string(),
+        1 => "every tick".to_string(),
+        period => format!("every {} ticks", period),
+    }
+}
+
+/// The tasks of a scheduler: the ones that were just scheduled and are still linked from `head`,
+/// the ones waiting in the `pending` queue and the ones in `runners`.
+fn scheduler_tasks(heap_dump: &HeapDump, scheduler: U8) -> Vec<U8> {
+    let mut tasks = Vec::new();
+
+    // head is a sentinel, the new tasks follow it. The list may be relinked while the
+    // heap dump is taken, so stop at the first task that was already seen.
+    let mut seen = HashSet::new();
+    let mut task = heap_dump
+        .reference_field(scheduler, "head")
+        .and_then(|head| heap_dump.reference_field(head, "next"));
+    while let Some(current) = task.filter(|task| seen.insert(*task)) {
+        tasks.push(current);
+        task = heap_dump.reference_field(current, "next");
+    }
+
+    if let Some(pending) = heap_dump.reference_field(scheduler, "pending") {
+        let size = match heap_dump.field_value(pending, "size") {
+            Some(Value::Int(size)) => *size as usize,
+            _ => 0,
+        };
+        if let Some(queue) = heap_dump
+            .reference_field(pending, "queue")
+            .and_then(|queue| heap_dump.array_elements(queue))
+        {
+            tasks.extend(queue.iter().take(size).filter(|task| **task != 0));
+        }
+    }
+
+    if let Some(runners) = heap_dump
+        .reference_field(scheduler, "runners")
+        .and_then(|runners| heap_dump.read_map(runners))
+    {
+        tasks.extend(
+            runners
+                .into_iter()
+                .map(|(_, task)| task)
+                .filter(|task| *task != 0),
+        );
+    }
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn tasks_are_collected_once_from_all_queues() {
+        let mut builder = HeapDumpBuilder::new();
+        let scheduler_class = builder.class(
+            "org/bukkit/craftbukkit/scheduler/CraftScheduler",
+            0,
+            0,
+            &["head", "pending", "runners"],
+        );
+        let task_class = builder.class(
+            "org/bukkit/craftbukkit/scheduler/CraftTask",
+            0,
+            0,
+            &["next", "rTask", "period", "plugin"],
+        );
+        let queue_class = builder.class("java/util/PriorityQueue", 0, 0, &["queue", "size"]);
+        let array_class = builder.class("[Ljava/lang/Object;", 0, 0, &[]);
+        let runnable_class = builder.class("example/Task", 0, 0, &[]);
+        let plugin_class = builder.class("example/ExamplePlugin", 0, 0, &[]);
+        let plugin = builder.instance(plugin_class, vec![]);
+        let task = |builder: &mut HeapDumpBuilder, period: i64| {
+            let runnable = builder.instance(runnable_class, vec![]);
+            builder.instance(
+                task_class,
+                vec![
+                    object(0),
+                    object(runnable),
+                    Value::Long(period),
+                    object(plugin),
+                ],
+            )
+        };
+        let head = task(&mut builder, -1);
+        let new = task(&mut builder, 20);
+        let queued = task(&mut builder, -1);
+        let removed = task(&mut builder, -2);
+        let running = task(&mut builder, 1);
+        // the list is being relinked and points back to an earlier task
+        builder.set_field(head, 0, object(new));
+        builder.set_field(new, 0, object(queued));
+        builder.set_field(queued, 0, object(new));
+        // the queue array still holds a task behind its size
+        let array = builder.object_array(array_class, vec![queued, running, removed]);
+        let pending = builder.instance(queue_class, vec![object(array), Value::Int(2)]);
+        let runners = builder.hash_map(vec![(0, running), (0, queued)]);
+        let scheduler = builder.instance(
+            scheduler_class,
+            vec![object(head), object(pending), object(runners)],
+        );
+        builder.root(scheduler);
+        let heap_dump = builder.build();
+
+        // the sentinel head is skipped, the removed task is behind the size of the queue
+        let tasks = scheduler_tasks(&heap_dump, scheduler);
+        assert_eq!(tasks, [new, queued, queued, running, running, queued]);
+
+        let groups = scheduled_tasks(&heap_dump, ReferenceFilter::All);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].plugin_object_id, Some(plugin));
+        assert_eq!(groups[0].task_class_object_id, Some(runnable_class));
+        let mut ids = groups[0]
+            .tasks
+            .iter()
+            .map(|task| task.object_id)
+            .collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, [new, queued, running]);
+    }
+
+    #[test]
+    fn periods_are_described() {
+        assert_eq!(describe_period(-1), "once");
+        assert_eq!(describe_period(-2), "cancelled");
+        assert_eq!(describe_period(-3), "future");
+        assert_eq!(describe_period(-4), "future");
+        assert_eq!(describe_period(1), "every tick");
+        assert_eq!(describe_period(20), "every 20 ticks");
+    }
+}
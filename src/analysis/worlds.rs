@@ -0,0 +1,299 @@
+use crate::analysis::platform::is_craftbukkit_class;
+use crate::heap_dump::{HeapDump, Reference, ReferenceFilter};
+use hprof_rs::hprof_model::{Value, U8};
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// the base classes of server levels, their chunk sources, chunks, entities and block entities,
+// by their Mojang, Spigot and intermediary names
+const LEVEL_CLASSES: [&str; 3] = [
+    "net/minecraft/server/level/ServerLevel",
+    "net/minecraft/server/level/WorldServer",
+    "net/minecraft/class_3218",
+];
+const CHUNK_SOURCE_CLASSES: [&str; 3] = [
+    "net/minecraft/server/level/ServerChunkCache",
+    "net/minecraft/server/level/ChunkProviderServer",
+    "net/minecraft/class_3215",
+];
+const CHUNK_CLASSES: [&str; 3] = [
+    "net/minecraft/world/level/chunk/LevelChunk",
+    "net/minecraft/world/level/chunk/Chunk",
+    "net/minecraft/class_2818",
+];
+const ENTITY_CLASSES: [&str; 2] = [
+    "net/minecraft/world/entity/Entity",
+    "net/minecraft/class_1297",
+];
+const BLOCK_ENTITY_CLASSES: [&str; 3] = [
+    "net/minecraft/world/level/block/entity/BlockEntity",
+    "net/minecraft/world/level/block/entity/TileEntity",
+    "net/minecraft/class_2586",
+];
+
+/// A `CraftWorld` together with the contents of its server level. Without the Bukkit API,
+/// e.g. on mod loaders, the world is the server level itself.
+#[derive(Clone)]
+pub struct World {
+    /// the `CraftWorld`, or the server level if there is none
+    pub object_id: U8,
+    /// the server level wrapped by the `CraftWorld`
+    pub handle_object_id: Option<U8>,
+    /// the name the world is registered under in the `CraftServer`
+    pub name: Option<String>,
+    pub chunk_count: usize,
+    /// the number of entities by their class, the most common classes first
+    pub entity_counts: Vec<(U8, usize)>,
+    pub block_entity_count: usize,
+    pub retained_size: u64,
+}
+
+impl World {
+    pub fn entity_count(&self) -> usize {
+        self.entity_counts.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Finds all worlds and counts the entities and block entities that refer to them, and the
+/// chunks held by their chunk source. The largest worlds come first.
+pub fn worlds(heap_dump: &HeapDump, filter: ReferenceFilter) -> Vec<World> {
+    let names = world_names(heap_dump);
+    let mut craft_worlds = craftbukkit_instances(heap_dump, "CraftWorld")
+        .map(|object_id| (object_id, heap_dump.reference_field(object_id, "world")))
+        .collect::<Vec<_>>();
+    if craft_worlds.is_empty() {
+        craft_worlds = instances_of(heap_dump, &LEVEL_CLASSES)
+            .map(|level| (level, Some(level)))
+            .collect();
+    }
+    let handles = craft_worlds
+        .iter()
+        .filter_map(|(_, handle)| *handle)
+        .collect::<HashSet<_>>();
+
+    // the fields are obfuscated on some servers, so the world of an object is the first
+    // server level any of its fields refers to
+    let world_of = |object_id: U8| {
+        let Some(Reference::Instance(instance)) = heap_dump.objects.get(&object_id).map(|r| &**r)
+        else {
+            return None;
+        };
+        instance.fields.iter().find_map(|value| match value {
+            Value::Object { object_id } if handles.contains(object_id) => Some(*object_id),
+            _ => None,
+        })
+    };
+    let chunks = instances_of(heap_dump, &CHUNK_CLASSES).collect::<HashSet<_>>();
+    let block_entities = instances_of(heap_dump, &BLOCK_ENTITY_CLASSES)
+        .filter_map(world_of)
+        .counts();
+    let mut entities = instances_of(heap_dump, &ENTITY_CLASSES)
+        .filter_map(|entity| Some((world_of(entity)?, heap_dump.class_of(entity)?)))
+        .into_group_map();
+
+    craft_worlds
+        .into_iter()
+        .map(|(object_id, handle_object_id)| {
+            let entity_counts = handle_object_id
+                .and_then(|handle| entities.remove(&handle))
+                .unwrap_or_default()
+                .into_iter()
+                .counts()
+                .into_iter()
+                .sorted_by_key(|(_, count)| Reverse(*count))
+                .collect();
+            let block_entity_count = handle_object_id
+                .and_then(|handle| block_entities.get(&handle).copied())
+                .unwrap_or(0);
+            let chunk_count = handle_object_id.map_or(0, |handle| {
+                chunk_count(heap_dump, handle, &handles, &chunks, &world_of, filter)
+            });
+            World {
+                object_id,
+                handle_object_id,
+                name: names.get(&object_id).cloned(),
+                chunk_count,
+                entity_counts,
+                block_entity_count,
+                retained_size: heap_dump
+                    .retained_size(handle_object_id.unwrap_or(object_id), filter)
+                    .unwrap_or(0),
+            }
+        })
+        .sorted_by_key(|world| Reverse(world.retained_size))
+        .collect()
+}
+
+/// Counts the chunks that can be reached from the chunk source of a level without passing
+/// through classes or other levels. Objects shared between levels may still lead to the chunks
+/// of other levels, so chunks referring to another level are not counted.
+fn chunk_count(
+    heap_dump: &HeapDump,
+    level: U8,
+    levels: &HashSet<U8>,
+    chunks: &HashSet<U8>,
+    world_of: &impl Fn(U8) -> Option<U8>,
+    filter: ReferenceFilter,
+) -> usize {
+    let chunk_source = heap_dump
+        .object_graph
+        .neighbors(level)
+        .find(|object_id| is_instance_of(heap_dump, *object_id, &CHUNK_SOURCE_CLASSES));
+    let Some(chunk_source) = chunk_source else {
+        return 0;
+    };
+    let mut count = 0;
+    let mut seen = HashSet::from([chunk_source]);
+    let mut queue = VecDeque::from([chunk_source]);
+    while let Some(node) = queue.pop_front() {
+        for (_, target, edge) in heap_dump.object_graph.edges(node) {
+            if !filter.includes(edge.kind) || !seen.insert(target) {
+                continue;
+            }
+            if chunks.contains(&target) {
+                if !matches!(world_of(target), Some(world) if world != level) {
+                    count += 1;
+                }
+            } else if !levels.contains(&target) && !heap_dump.classes.contains_key(&target) {
+                queue.push_back(target);
+            }
+        }
+    }
+    count
+}
+
+/// Whether an object is an instance of one of the given classes or of a subclass.
+fn is_instance_of(heap_dump: &HeapDump, object_id: U8, class_names: &[&str]) -> bool {
+    heap_dump
+        .class_of(object_id)
+        .is_some_and(|class_object_id| {
+            class_names
+                .iter()
+                .any(|class_name| heap_dump.is_subclass_of(class_object_id, class_name))
+        })
+}
+
+/// The names of the `CraftWorld`s, read from the `worlds` map of the `CraftServer`.
+fn world_names(heap_dump: &HeapDump) -> HashMap<U8, String> {
+    craftbukkit_instances(heap_dump, "CraftServer")
+        .filter_map(|server| heap_dump.reference_field(server, "worlds"))
+        .filter_map(|worlds| heap_dump.read_map(worlds))
+        .flatten()
+        .filter_map(|(name, world)| Some((world, heap_dump.read_string(name)?)))
+        .collect()
+}
+
+/// The instances of all classes that are or extend one of the given classes.
+fn instances_of<'a>(
+    heap_dump: &'a HeapDump,
+    class_names: &'a [&'a str],
+) -> impl Iterator<Item = U8> + 'a {
+    heap_dump
+        .classes
+        .values()
+        .filter(move |class_info| {
+            class_names
+                .iter()
+                .any(|class_name| heap_dump.is_subclass_of(class_info.class_object_id, class_name))
+        })
+        .flat_map(move |class_info| heap_dump.instances(class_info.class_object_id))
+        .map(|instance| instance.object_id())
+}
+
+/// The instances of a CraftBukkit class, see [is_craftbukkit_class].
+fn craftbukkit_instances<'a>(
+    heap_dump: &'a HeapDump,
+    name: &'a str,
+) -> impl Iterator<Item = U8> + 'a {
+    heap_dump
+        .classes
+        .values()
+        .filter(move |class_info| {
+            is_craftbukkit_class(heap_dump.class_name(class_info.class_object_id), name)
+        })
+        .flat_map(move |class_info| heap_dump.instances(class_info.class_object_id))
+        .map(|instance| instance.object_id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::heap_dump::tests::{object, HeapDumpBuilder};
+
+    #[test]
+    fn chunks_are_counted_from_the_chunk_source_of_each_level() {
+        let mut builder = HeapDumpBuilder::new();
+        let level_class = builder.class(
+            "net/minecraft/server/level/ServerLevel",
+            0,
+            0,
+            &["chunkSource"],
+        );
+        let chunk_source_class = builder.class(
+            "net/minecraft/server/level/ServerChunkCache",
+            0,
+            0,
+            &["chunks", "shared"],
+        );
+        let chunk_class = builder.class(
+            "net/minecraft/world/level/chunk/LevelChunk",
+            0,
+            0,
+            &["level"],
+        );
+        let entity_class = builder.class("net/minecraft/world/entity/Entity", 0, 0, &["level"]);
+        let zombie_class = builder.class(
+            "net/minecraft/world/entity/monster/Zombie",
+            entity_class,
+            0,
+            &[],
+        );
+        let shared_class = builder.class("example/Shared", 0, 0, &["chunks"]);
+
+        let overworld = builder.instance(level_class, vec![object(0)]);
+        let nether = builder.instance(level_class, vec![object(0)]);
+        let overworld_chunks = (0..3)
+            .map(|_| builder.instance(chunk_class, vec![object(overworld)]))
+            .collect::<Vec<_>>();
+        let nether_chunk = builder.instance(chunk_class, vec![object(nether)]);
+        // an unloaded chunk that is still held elsewhere is not part of the chunk map
+        let unloaded_chunk = builder.instance(chunk_class, vec![object(overworld)]);
+        builder.root(unloaded_chunk);
+        // both chunk sources refer to an object that leads to a chunk of the nether
+        let shared = builder.list(vec![nether_chunk]);
+        let overworld_map = builder.list(overworld_chunks);
+        let nether_map = builder.list(vec![nether_chunk]);
+        let overworld_source = builder.instance(
+            chunk_source_class,
+            vec![object(overworld_map), object(shared)],
+        );
+        let nether_source =
+            builder.instance(chunk_source_class, vec![object(nether_map), object(shared)]);
+        builder.set_field(overworld, 0, object(overworld_source));
+        builder.set_field(nether, 0, object(nether_source));
+        let holder = builder.instance(shared_class, vec![object(shared)]);
+        builder.root(holder);
+        builder.instance(zombie_class, vec![object(overworld)]);
+        builder.instance(zombie_class, vec![object(overworld)]);
+        builder.instance(entity_class, vec![object(nether)]);
+        builder.root(overworld);
+        builder.root(nether);
+        let heap_dump = builder.build();
+
+        // without CraftWorlds, e.g. on Fabric, the levels are the worlds
+        let worlds = worlds(&heap_dump, ReferenceFilter::All);
+        assert_eq!(worlds.len(), 2);
+        let world = |level| {
+            worlds
+                .iter()
+                .find(|world| world.object_id == level)
+                .unwrap()
+        };
+        assert_eq!(world(overworld).handle_object_id, Some(overworld));
+        assert_eq!(world(overworld).chunk_count, 3);
+        assert_eq!(world(overworld).entity_counts, [(zombie_class, 2)]);
+        assert_eq!(world(nether).chunk_count, 1);
+        assert_eq!(world(nether).entity_counts, [(entity_class, 1)]);
+    }
+}
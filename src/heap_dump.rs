@@ -1,9 +1,15 @@
+use crate::analysis::platform::{detect_platform, PlatformProfile};
 use chrono::{DateTime, Utc};
 use hprof_rs::hprof_model::{HeapDumpTag, RecordTag, Value, U8};
 use hprof_rs::reader::HprofReader;
+use itertools::Itertools;
 use multimap::MultiMap;
+use petgraph::algo::dominators;
 use petgraph::graphmap::DiGraphMap;
-use std::collections::HashMap;
+use petgraph::visit::EdgeFiltered;
+use petgraph::Direction;
+use std::cell::OnceCell;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::{Hash, Hasher};
 use std::io::{Read, Seek};
 use std::rc::Rc;
@@ -11,7 +17,116 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 static COUNTER: AtomicU64 = AtomicU64::new(0);
 
-pub const FAKE_ROOT_ID: U8 = U8::MAX; 
+pub const FAKE_ROOT_ID: U8 = U8::MAX;
+
+const OBJECT_HEADER_SIZE: u64 = 16;
+const ARRAY_HEADER_SIZE: u64 = 16;
+const ID_SIZE: u64 = 8;
+/// Strings in previews are cut off after this many characters.
+const MAX_PREVIEW_LENGTH: usize = 50;
+
+/// How strongly a reference keeps its target alive. Everything except the
+/// `referent` of a `java.lang.ref.Reference` is a strong reference.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ReferenceKind {
+    Strong,
+    Soft,
+    Weak,
+    Final,
+    Phantom,
+}
+
+impl ReferenceKind {
+    pub fn is_strong(self) -> bool {
+        self == ReferenceKind::Strong
+    }
+}
+
+/// The kind of GC root an object is referenced by.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum RootKind {
+    ThreadObject,
+    JniGlobal,
+    JniLocal,
+    JavaFrame,
+    StickyClass,
+}
+
+impl RootKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            RootKind::ThreadObject => "thread object",
+            RootKind::JniGlobal => "JNI global",
+            RootKind::JniLocal => "JNI local",
+            RootKind::JavaFrame => "Java frame",
+            RootKind::StickyClass => "sticky class",
+        }
+    }
+}
+
+/// Where a reference in the object graph comes from.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum EdgeOrigin {
+    /// an instance field, the referring object is an instance of (a subclass of) the declaring class
+    InstanceField(FieldSlot),
+    /// a static field, the referring object is the declaring class
+    StaticField(FieldSlot),
+    ArrayElement(usize),
+    /// an instance field without a declaration in the class dumps, identified by its index
+    UnknownField(usize),
+    /// the implicit reference from an object to its class
+    Class,
+    /// the implicit reference from a class to the loader that defined it
+    ClassLoader,
+    /// an edge from the fake common root to an actual root object
+    Root(RootKind),
+}
+
+/// The weight of an edge in the object graph.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Edge {
+    pub kind: ReferenceKind,
+    pub origin: EdgeOrigin,
+}
+
+impl Edge {
+    fn strong(origin: EdgeOrigin) -> Edge {
+        Edge {
+            kind: ReferenceKind::Strong,
+            origin,
+        }
+    }
+}
+
+/// Selects which edges of the object graph an analysis follows.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ReferenceFilter {
+    All,
+    /// Ignore soft, weak, final and phantom references, i.e. only follow
+    /// references that actually prevent the GC from collecting the target.
+    #[default]
+    StrongOnly,
+}
+
+impl ReferenceFilter {
+    pub fn from_strong_only(strong_only: bool) -> ReferenceFilter {
+        if strong_only {
+            ReferenceFilter::StrongOnly
+        } else {
+            ReferenceFilter::All
+        }
+    }
+
+    pub fn includes(self, kind: ReferenceKind) -> bool {
+        match self {
+            ReferenceFilter::All => true,
+            ReferenceFilter::StrongOnly => kind.is_strong(),
+        }
+    }
+}
+
+/// The object graph without the edges a [ReferenceFilter] rejects.
+pub type FilteredGraph<'a, F> = EdgeFiltered<&'a DiGraphMap<U8, Edge>, F>;
 
 pub struct HeapDump {
     id: u64,
@@ -20,7 +135,12 @@ pub struct HeapDump {
     pub classes: HashMap<U8, AnalysisClassInfo>,
     pub objects: HashMap<U8, Rc<Reference>>,
     pub objects_by_class: MultiMap<U8, Rc<Reference>>,
-    pub object_graph: DiGraphMap<U8, ()>,
+    pub object_graph: DiGraphMap<U8, Edge>,
+    /// the [instance_field_layout] of every class that has instances
+    field_layouts: HashMap<U8, Vec<FieldSlot>>,
+    dominator_tree: OnceCell<DominatorTree>,
+    strong_dominator_tree: OnceCell<DominatorTree>,
+    platform: OnceCell<&'static PlatformProfile>,
 }
 
 impl HeapDump {
@@ -30,21 +150,57 @@ impl HeapDump {
         classes: HashMap<U8, AnalysisClassInfo>,
         objects: HashMap<U8, Rc<Reference>>,
         objects_by_class: MultiMap<U8, Rc<Reference>>,
-        roots: Vec<U8>,
+        roots: Vec<(U8, RootKind)>,
     ) -> HeapDump {
-        let mut object_graph: DiGraphMap<U8, ()> =
+        let mut object_graph: DiGraphMap<U8, Edge> =
             DiGraphMap::with_capacity(objects.len(), objects.len() * 2);
 
+        let referent_fields = referent_fields(&names, &classes);
+        let mut field_layouts = HashMap::new();
+
         for object in objects.values() {
             match &**object {
                 Reference::Instance(instance) => {
-                    for value in &instance.fields {
+                    add_reference(
+                        &mut object_graph,
+                        instance.object_id,
+                        instance.class_object_id,
+                        Edge::strong(EdgeOrigin::Class),
+                    );
+                    let referent_field = referent_fields.get(&instance.class_object_id);
+                    let field_layout: &Vec<FieldSlot> = field_layouts
+                        .entry(instance.class_object_id)
+                        .or_insert_with(|| {
+                            instance_field_layout(&classes, instance.class_object_id)
+                        });
+                    // a class missing from the dump leaves the layout shorter than the values,
+                    // the references in the remaining values must not get lost
+                    for (index, value) in instance.fields.iter().enumerate() {
+                        let kind = match referent_field {
+                            Some(&(kind, referent_index)) if referent_index == index => kind,
+                            _ => ReferenceKind::Strong,
+                        };
+                        let origin = match field_layout.get(index) {
+                            Some(field) => EdgeOrigin::InstanceField(*field),
+                            None => EdgeOrigin::UnknownField(index),
+                        };
+                        let edge = Edge { kind, origin };
                         match value {
                             Value::Object { object_id } => {
-                                object_graph.add_edge(instance.object_id, *object_id, ());
+                                add_reference(
+                                    &mut object_graph,
+                                    instance.object_id,
+                                    *object_id,
+                                    edge,
+                                );
                             }
                             Value::Array { object_id } => {
-                                object_graph.add_edge(instance.object_id, *object_id, ());
+                                add_reference(
+                                    &mut object_graph,
+                                    instance.object_id,
+                                    *object_id,
+                                    edge,
+                                );
                             }
                             Value::Byte(_) => {}
                             Value::Char(_) => {}
@@ -58,17 +214,59 @@ impl HeapDump {
                     }
                 }
                 Reference::ObjectArray(array) => {
-                    for object_id in &array.values {
-                        object_graph.add_edge(array.object_id, *object_id, ());
+                    add_reference(
+                        &mut object_graph,
+                        array.object_id,
+                        array.class_object_id,
+                        Edge::strong(EdgeOrigin::Class),
+                    );
+                    for (index, object_id) in array.values.iter().enumerate() {
+                        add_reference(
+                            &mut object_graph,
+                            array.object_id,
+                            *object_id,
+                            Edge::strong(EdgeOrigin::ArrayElement(index)),
+                        );
                     }
                 }
                 Reference::PrimitiveArray(_) => {} // has no outgoing references
-                Reference::FakeCommonRoot => panic!("unexpected fake root")
+                Reference::FakeCommonRoot => panic!("unexpected fake root"),
             }
         }
 
-        for root in roots {
-            object_graph.add_edge(FAKE_ROOT_ID, root, ());
+        for class_info in classes.values() {
+            add_reference(
+                &mut object_graph,
+                class_info.class_object_id,
+                class_info.class_loader_object_id,
+                Edge::strong(EdgeOrigin::ClassLoader),
+            );
+            for field in &class_info.static_fields {
+                let object_id = match field.value {
+                    Value::Object { object_id } => object_id,
+                    Value::Array { object_id } => object_id,
+                    _ => continue,
+                };
+                let origin = EdgeOrigin::StaticField(FieldSlot {
+                    declaring_class_object_id: class_info.class_object_id,
+                    name_id: field.name_id,
+                });
+                add_reference(
+                    &mut object_graph,
+                    class_info.class_object_id,
+                    object_id,
+                    Edge::strong(origin),
+                );
+            }
+        }
+
+        for (root, kind) in roots {
+            add_reference(
+                &mut object_graph,
+                FAKE_ROOT_ID,
+                root,
+                Edge::strong(EdgeOrigin::Root(kind)),
+            );
         }
 
         HeapDump {
@@ -79,8 +277,805 @@ impl HeapDump {
             objects,
             objects_by_class,
             object_graph,
+            field_layouts,
+            dominator_tree: OnceCell::new(),
+            strong_dominator_tree: OnceCell::new(),
+            platform: OnceCell::new(),
+        }
+    }
+
+    /// The object graph restricted to the edges accepted by `filter`.
+    pub fn filtered_graph<'a>(
+        &'a self,
+        filter: ReferenceFilter,
+    ) -> FilteredGraph<'a, impl Fn((U8, U8, &'a Edge)) -> bool + 'a> {
+        EdgeFiltered::from_fn(
+            &self.object_graph,
+            move |(_, _, edge): (U8, U8, &'a Edge)| filter.includes(edge.kind),
+        )
+    }
+
+    /// Finds a shortest path from a GC root to the given object.
+    /// The path starts at the root object and ends with `object_id`.
+    pub fn path_to_root(&self, object_id: U8, filter: ReferenceFilter) -> Option<Vec<U8>> {
+        // maps each visited node to its successor on the way to object_id
+        let mut successors = HashMap::new();
+        let mut queue = VecDeque::new();
+        successors.insert(object_id, object_id);
+        queue.push_back(object_id);
+        while let Some(node) = queue.pop_front() {
+            for (referrer, _, edge) in self.object_graph.edges_directed(node, Direction::Incoming) {
+                if !filter.includes(edge.kind) || successors.contains_key(&referrer) {
+                    continue;
+                }
+                if referrer == FAKE_ROOT_ID {
+                    let mut path = vec![node];
+                    let mut current = node;
+                    while current != object_id {
+                        current = successors[&current];
+                        path.push(current);
+                    }
+                    return Some(path);
+                }
+                successors.insert(referrer, node);
+                queue.push_back(referrer);
+            }
+        }
+        None
+    }
+
+    pub fn class_name(&self, class_object_id: U8) -> &str {
+        self.classes
+            .get(&class_object_id)
+            .and_then(|class_info| self.names.get(&class_info.class_name_id))
+            .map(|name| name.as_str())
+            .unwrap_or("<< no name >>")
+    }
+
+    pub fn name(&self, name_id: U8) -> &str {
+        self.names
+            .get(&name_id)
+            .map(|name| name.as_str())
+            .unwrap_or("<< no name >>")
+    }
+
+    /// All instances of a class. Primitive arrays are not included.
+    pub fn instances(&self, class_object_id: U8) -> &[Rc<Reference>] {
+        self.objects_by_class
+            .get_vec(&class_object_id)
+            .map(|instances| instances.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// The class of an instance or object array. Primitive arrays and classes have no class id.
+    pub fn class_of(&self, object_id: U8) -> Option<U8> {
+        match self.objects.get(&object_id).map(|reference| &**reference) {
+            Some(Reference::Instance(instance)) => Some(instance.class_object_id),
+            Some(Reference::ObjectArray(array)) => Some(array.class_object_id),
+            _ => None,
         }
     }
+
+    /// The direct subclasses of a class.
+    pub fn subclasses(&self, class_object_id: U8) -> Vec<U8> {
+        self.classes
+            .values()
+            .filter(|class_info| class_info.super_class_object_id == class_object_id)
+            .map(|class_info| class_info.class_object_id)
+            .sorted_by_key(|class_id| self.class_name(*class_id))
+            .collect()
+    }
+
+    /// The name of the type of an object, including primitive arrays and classes.
+    pub fn type_name(&self, object_id: U8) -> String {
+        if self.classes.contains_key(&object_id) {
+            return "java/lang/Class".to_string();
+        }
+        match self.objects.get(&object_id).map(|reference| &**reference) {
+            Some(Reference::Instance(instance)) => {
+                self.class_name(instance.class_object_id).to_string()
+            }
+            Some(Reference::ObjectArray(array)) => {
+                self.class_name(array.class_object_id).to_string()
+            }
+            Some(Reference::PrimitiveArray(array)) => format!(
+                "{}[]",
+                array
+                    .values
+                    .first()
+                    .map(primitive_type_name)
+                    .unwrap_or("primitive")
+            ),
+            Some(Reference::FakeCommonRoot) | None => "<< unknown >>".to_string(),
+        }
+    }
+
+    /// A short human-readable description of an object, its type followed by its id.
+    pub fn describe_object(&self, object_id: U8) -> String {
+        if self.classes.contains_key(&object_id) {
+            return format!("class {}", self.class_name(object_id));
+        }
+        let description = format!("{} @ {:#x}", self.type_name(object_id), object_id);
+        match self.preview(object_id) {
+            Some(preview) => format!("{} = {}", description, preview),
+            None => description,
+        }
+    }
+
+    /// A short preview of the value of well-known objects: the text of strings, the value of
+    /// boxed primitives, the name of enum constants and classes and the canonical form of UUIDs.
+    pub fn preview(&self, object_id: U8) -> Option<String> {
+        if self.classes.contains_key(&object_id) {
+            return Some(self.class_name(object_id).to_string());
+        }
+        let class_object_id = self.class_of(object_id)?;
+        match self.class_name(class_object_id) {
+            "java/lang/String" => {
+                let value = self.read_string(object_id)?;
+                let mut preview = value.chars().take(MAX_PREVIEW_LENGTH).collect::<String>();
+                if value.chars().count() > MAX_PREVIEW_LENGTH {
+                    preview.push('…');
+                }
+                Some(format!("{:?}", preview))
+            }
+            "java/lang/Boolean"
+            | "java/lang/Byte"
+            | "java/lang/Character"
+            | "java/lang/Short"
+            | "java/lang/Integer"
+            | "java/lang/Long"
+            | "java/lang/Float"
+            | "java/lang/Double" => self.field_value(object_id, "value").map(format_primitive),
+            "java/util/UUID" => self.read_uuid(object_id),
+            _ if self.is_subclass_of(class_object_id, "java/lang/Enum") => self
+                .reference_field(object_id, "name")
+                .and_then(|name| self.read_string(name)),
+            _ => None,
+        }
+    }
+
+    /// All objects that reference the given object, together with the edge they reference it by.
+    /// References from the fake common root are not included, see [HeapDump::root_kind].
+    pub fn referrers(&self, object_id: U8) -> Vec<(U8, Edge)> {
+        self.object_graph
+            .edges_directed(object_id, Direction::Incoming)
+            .filter(|(referrer, _, _)| *referrer != FAKE_ROOT_ID)
+            .map(|(referrer, _, edge)| (referrer, *edge))
+            .collect()
+    }
+
+    /// The kind of GC root the given object is, if any.
+    pub fn root_kind(&self, object_id: U8) -> Option<RootKind> {
+        match self.object_graph.edge_weight(FAKE_ROOT_ID, object_id) {
+            Some(Edge {
+                origin: EdgeOrigin::Root(kind),
+                ..
+            }) => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// A human-readable description of where a reference comes from, e.g. `java/util/HashMap$Node.value`.
+    pub fn describe_edge(&self, edge: &Edge) -> String {
+        let origin = match edge.origin {
+            EdgeOrigin::InstanceField(field) => format!(
+                "{}.{}",
+                self.class_name(field.declaring_class_object_id),
+                self.name(field.name_id)
+            ),
+            EdgeOrigin::StaticField(field) => format!(
+                "static {}.{}",
+                self.class_name(field.declaring_class_object_id),
+                self.name(field.name_id)
+            ),
+            EdgeOrigin::ArrayElement(index) => format!("[{}]", index),
+            EdgeOrigin::UnknownField(index) => format!("<field #{}>", index),
+            EdgeOrigin::Class => "<class>".to_string(),
+            EdgeOrigin::ClassLoader => "<class loader>".to_string(),
+            EdgeOrigin::Root(kind) => format!("GC root ({})", kind.label()),
+        };
+        match edge.kind {
+            ReferenceKind::Strong => origin,
+            ReferenceKind::Soft => format!("{} (soft)", origin),
+            ReferenceKind::Weak => format!("{} (weak)", origin),
+            ReferenceKind::Final => format!("{} (final)", origin),
+            ReferenceKind::Phantom => format!("{} (phantom)", origin),
+        }
+    }
+
+    /// Describes all references from one object to another, e.g. both the key and the value of
+    /// a map entry. The object graph only keeps the strongest of them as the edge between the
+    /// two objects, which is described if the references cannot be found, e.g. for GC roots.
+    pub fn describe_edges(&self, from: U8, to: U8, edge: &Edge) -> String {
+        let edges = self.edges_between(from, to);
+        if edges.is_empty() {
+            return self.describe_edge(edge);
+        }
+        edges.iter().map(|edge| self.describe_edge(edge)).join(", ")
+    }
+
+    /// All references from one object to another, in the order of the fields or elements.
+    fn edges_between(&self, from: U8, to: U8) -> Vec<Edge> {
+        let mut edges = Vec::new();
+        if let Some(class_info) = self.classes.get(&from) {
+            if class_info.class_loader_object_id == to {
+                edges.push(Edge::strong(EdgeOrigin::ClassLoader));
+            }
+            edges.extend(
+                class_info
+                    .static_fields
+                    .iter()
+                    .filter(|field| reference_of(&field.value) == Some(to))
+                    .map(|field| {
+                        Edge::strong(EdgeOrigin::StaticField(FieldSlot {
+                            declaring_class_object_id: from,
+                            name_id: field.name_id,
+                        }))
+                    }),
+            );
+            return edges;
+        }
+        match self.objects.get(&from).map(|reference| &**reference) {
+            Some(Reference::Instance(instance)) => {
+                if instance.class_object_id == to {
+                    edges.push(Edge::strong(EdgeOrigin::Class));
+                }
+                let field_layout = self.field_layout(instance.class_object_id);
+                for (index, value) in instance.fields.iter().enumerate() {
+                    if reference_of(value) != Some(to) {
+                        continue;
+                    }
+                    let edge = match field_layout.get(index) {
+                        Some(field) => {
+                            let is_referent = self.name(field.name_id) == "referent"
+                                && self.class_name(field.declaring_class_object_id)
+                                    == "java/lang/ref/Reference";
+                            let kind = is_referent
+                                .then(|| {
+                                    referent_kind(
+                                        &self.names,
+                                        &self.classes,
+                                        instance.class_object_id,
+                                    )
+                                })
+                                .flatten()
+                                .unwrap_or(ReferenceKind::Strong);
+                            Edge {
+                                kind,
+                                origin: EdgeOrigin::InstanceField(*field),
+                            }
+                        }
+                        None => Edge::strong(EdgeOrigin::UnknownField(index)),
+                    };
+                    edges.push(edge);
+                }
+            }
+            Some(Reference::ObjectArray(array)) => {
+                if array.class_object_id == to {
+                    edges.push(Edge::strong(EdgeOrigin::Class));
+                }
+                edges.extend(
+                    array
+                        .values
+                        .iter()
+                        .positions(|object_id| *object_id == to)
+                        .map(|index| Edge::strong(EdgeOrigin::ArrayElement(index))),
+                );
+            }
+            _ => {}
+        }
+        edges
+    }
+
+    /// The number of bytes an object occupies itself, estimated from its fields
+    /// or elements. Ids that do not belong to an object (e.g. classes) have a size of 0.
+    pub fn shallow_size(&self, object_id: U8) -> u64 {
+        self.objects
+            .get(&object_id)
+            .map(|reference| reference.shallow_size())
+            .unwrap_or(0)
+    }
+
+    /// The number of bytes that would be freed if the given object was collected,
+    /// or `None` if the object is not reachable from any GC root.
+    pub fn retained_size(&self, object_id: U8, filter: ReferenceFilter) -> Option<u64> {
+        self.dominator_tree(filter)
+            .retained_sizes
+            .get(&object_id)
+            .copied()
+    }
+
+    /// The dominator tree of the object graph, rooted at [FAKE_ROOT_ID].
+    /// It is computed once per filter.
+    pub fn dominator_tree(&self, filter: ReferenceFilter) -> &DominatorTree {
+        let cell = match filter {
+            ReferenceFilter::All => &self.dominator_tree,
+            ReferenceFilter::StrongOnly => &self.strong_dominator_tree,
+        };
+        cell.get_or_init(|| self.compute_dominator_tree(filter))
+    }
+
+    /// The platform the heap dump was taken on. It is detected once, see [detect_platform].
+    pub fn platform(&self) -> &'static PlatformProfile {
+        self.platform.get_or_init(|| detect_platform(self))
+    }
+
+    fn compute_dominator_tree(&self, filter: ReferenceFilter) -> DominatorTree {
+        let graph = self.filtered_graph(filter);
+        let dominators = dominators::simple_fast(&graph, FAKE_ROOT_ID);
+        let mut dominated: HashMap<U8, Vec<U8>> = HashMap::new();
+        let mut immediate_dominators = HashMap::new();
+        for node in self.object_graph.nodes() {
+            if let Some(dominator) = dominators.immediate_dominator(node) {
+                dominated.entry(dominator).or_default().push(node);
+                immediate_dominators.insert(node, dominator);
+            }
+        }
+
+        // sum up the dominator tree bottom-up, children are always visited before their parent
+        let mut retained_sizes = HashMap::with_capacity(self.object_graph.node_count());
+        let mut stack = vec![(FAKE_ROOT_ID, false)];
+        while let Some((node, children_visited)) = stack.pop() {
+            let children = dominated.get(&node);
+            if children_visited {
+                let children_size: u64 = children
+                    .map(|children| children.iter().map(|child| retained_sizes[child]).sum())
+                    .unwrap_or(0);
+                retained_sizes.insert(node, self.shallow_size(node) + children_size);
+            } else {
+                stack.push((node, true));
+                for child in children.into_iter().flatten() {
+                    stack.push((*child, false));
+                }
+            }
+        }
+        DominatorTree {
+            dominated,
+            immediate_dominators,
+            retained_sizes,
+        }
+    }
+
+    /// The retained size of groups of objects, e.g. all instances of classes defined by the
+    /// same class loader. `group` assigns objects to a group, objects without a group are ignored.
+    /// Objects of a group that are dominated by other objects of the same group are only counted once.
+    pub fn retained_size_by_group<K: Copy + Eq + Hash>(
+        &self,
+        filter: ReferenceFilter,
+        group: impl Fn(U8) -> Option<K>,
+    ) -> HashMap<K, u64> {
+        let tree = self.dominator_tree(filter);
+        let mut retained_sizes = HashMap::new();
+        // how often each group occurs on the current path of the dominator tree
+        let mut active_groups: HashMap<K, usize> = HashMap::new();
+        let mut stack = vec![(FAKE_ROOT_ID, false)];
+        while let Some((node, leaving)) = stack.pop() {
+            let key = group(node);
+            if leaving {
+                if let Some(count) = key.and_then(|key| active_groups.get_mut(&key)) {
+                    *count -= 1;
+                }
+                continue;
+            }
+            if let Some(key) = key {
+                let count = active_groups.entry(key).or_insert(0);
+                if *count == 0 {
+                    *retained_sizes.entry(key).or_insert(0) += tree.retained_sizes[&node];
+                }
+                *count += 1;
+            }
+            stack.push((node, true));
+            for child in tree.dominated.get(&node).into_iter().flatten() {
+                stack.push((*child, false));
+            }
+        }
+        retained_sizes
+    }
+
+    /// Whether the class or one of its superclasses has the given name.
+    pub fn is_subclass_of(&self, class_object_id: U8, class_name: &str) -> bool {
+        super_classes(&self.classes, class_object_id)
+            .any(|class_info| self.class_name(class_info.class_object_id) == class_name)
+    }
+
+    /// The [instance_field_layout] of a class, empty if the class has no instances.
+    pub fn field_layout(&self, class_object_id: U8) -> &[FieldSlot] {
+        self.field_layouts
+            .get(&class_object_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The value of an instance field. If several classes in the hierarchy declare a field
+    /// with this name, the one of the most specific class is returned.
+    pub fn field_value(&self, object_id: U8, field_name: &str) -> Option<&Value> {
+        let Some(Reference::Instance(instance)) = self.objects.get(&object_id).map(|r| &**r) else {
+            return None;
+        };
+        self.field_layout(instance.class_object_id)
+            .iter()
+            .zip(&instance.fields)
+            .find(|(field, _)| self.name(field.name_id) == field_name)
+            .map(|(_, value)| value)
+    }
+
+    /// The object referenced by an instance field, or `None` if the field does not exist,
+    /// is not a reference or is `null`.
+    pub fn reference_field(&self, object_id: U8, field_name: &str) -> Option<U8> {
+        match self.field_value(object_id, field_name) {
+            Some(Value::Object { object_id }) if *object_id != 0 => Some(*object_id),
+            Some(Value::Array { object_id }) if *object_id != 0 => Some(*object_id),
+            _ => None,
+        }
+    }
+
+    /// Decodes the contents of a `java.lang.String`, supporting both the compact `byte[]`
+    /// layout with a `coder` (Java 9+) and the older `char[]` layout. Before Java 7u6,
+    /// strings could share their array and only used the part given by `offset` and `count`.
+    pub fn read_string(&self, object_id: U8) -> Option<String> {
+        let value = self.reference_field(object_id, "value")?;
+        let Some(Reference::PrimitiveArray(array)) = self.objects.get(&value).map(|r| &**r) else {
+            return None;
+        };
+        let utf16 = match self.field_value(object_id, "coder") {
+            Some(Value::Byte(coder)) => *coder != 0,
+            _ => false,
+        };
+        // old strings share their array and only use the part from offset to offset + count,
+        // which is counted in chars, i.e. in pairs of bytes for UTF-16 bytes. An offset outside
+        // of the array falls back to the whole value and a count beyond it is clamped.
+        let width = match array.values.first() {
+            Some(Value::Byte(_)) if utf16 => 2,
+            _ => 1,
+        };
+        let length = array.values.len();
+        let range = match (
+            self.field_value(object_id, "offset"),
+            self.field_value(object_id, "count"),
+        ) {
+            (Some(Value::Int(offset)), Some(Value::Int(count))) => {
+                match (usize::try_from(*offset), usize::try_from(*count)) {
+                    (Ok(offset), Ok(count)) if offset.saturating_mul(width) <= length => {
+                        let end = offset.saturating_add(count).saturating_mul(width);
+                        offset * width..end.min(length)
+                    }
+                    _ => 0..length,
+                }
+            }
+            _ => 0..length,
+        };
+        let values = &array.values[range];
+        let bytes = || {
+            values.iter().filter_map(|value| match value {
+                Value::Byte(byte) => Some(*byte as u8),
+                _ => None,
+            })
+        };
+        match array.values.first() {
+            None => Some(String::new()),
+            Some(Value::Char(_)) => {
+                let chars = values
+                    .iter()
+                    .filter_map(|value| match value {
+                        Value::Char(unit) => Some(*unit),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                Some(String::from_utf16_lossy(&chars))
+            }
+            // UTF-16 strings are stored in the byte order of the JVM's platform, usually little endian
+            Some(Value::Byte(_)) if utf16 => {
+                let chars = bytes()
+                    .tuples()
+                    .map(|(low, high)| u16::from_le_bytes([low, high]))
+                    .collect::<Vec<_>>();
+                Some(String::from_utf16_lossy(&chars))
+            }
+            Some(Value::Byte(_)) => Some(bytes().map(char::from).collect()),
+            Some(_) => None,
+        }
+    }
+
+    /// The elements of the common `java.util.List` implementations,
+    /// or `None` if the object is not a list of a supported type.
+    pub fn read_list(&self, object_id: U8) -> Option<Vec<U8>> {
+        let elements = |array_field: &str| {
+            self.reference_field(object_id, array_field)
+                .and_then(|array_id| self.array_elements(array_id))
+                .map(<[U8]>::to_vec)
+        };
+        let size = |size_field: &str| match self.field_value(object_id, size_field) {
+            Some(Value::Int(size)) => Some(*size as usize),
+            _ => None,
+        };
+        let list = match self.type_name(object_id).as_str() {
+            "java/util/ArrayList" => {
+                let mut elements = elements("elementData")?;
+                elements.truncate(size("size")?);
+                elements
+            }
+            "java/util/Vector" => {
+                let mut elements = elements("elementData")?;
+                elements.truncate(size("elementCount")?);
+                elements
+            }
+            "java/util/concurrent/CopyOnWriteArrayList"
+            | "com/google/common/collect/RegularImmutableList" => elements("array")?,
+            "java/util/Arrays$ArrayList" => elements("a")?,
+            "java/util/ImmutableCollections$ListN" => elements("elements")?,
+            "java/util/ImmutableCollections$List12" => ["e0", "e1"]
+                .iter()
+                .filter_map(|field| self.reference_field(object_id, field))
+                // a list with a single element uses a plain object as placeholder for e1
+                .filter(|element| self.type_name(*element) != "java/lang/Object")
+                .collect(),
+            "java/util/Collections$SingletonList"
+            | "com/google/common/collect/SingletonImmutableList" => {
+                vec![self.reference_field(object_id, "element")?]
+            }
+            "java/util/Collections$EmptyList" => Vec::new(),
+            "java/util/Collections$UnmodifiableList"
+            | "java/util/Collections$UnmodifiableRandomAccessList"
+            | "java/util/Collections$SynchronizedList"
+            | "java/util/Collections$SynchronizedRandomAccessList" => {
+                return self.read_list(self.reference_field(object_id, "list")?);
+            }
+            _ => return None,
+        };
+        Some(list.into_iter().filter(|element| *element != 0).collect())
+    }
+
+    /// The entries of the common hash based `java.util.Map` implementations as pairs of key and
+    /// value, where `null` is 0. `None` if the object is not a map of a supported type.
+    pub fn read_map(&self, object_id: U8) -> Option<Vec<(U8, U8)>> {
+        match self.type_name(object_id).as_str() {
+            "java/util/HashMap"
+            | "java/util/LinkedHashMap"
+            | "java/util/WeakHashMap"
+            | "java/util/Hashtable"
+            | "java/util/concurrent/ConcurrentHashMap" => {}
+            "java/util/Collections$UnmodifiableMap" | "java/util/Collections$SynchronizedMap" => {
+                return self.read_map(self.reference_field(object_id, "m")?);
+            }
+            _ => return None,
+        }
+        // the table is only allocated once the first entry is added
+        let Some(table) = self.reference_field(object_id, "table") else {
+            return Some(Vec::new());
+        };
+        let mut entries = Vec::new();
+        // nodes and tables that were read, a node seen twice means that a bin was relinked
+        // while the heap dump was taken
+        let mut seen = HashSet::from([table]);
+        let mut tables = vec![table];
+        while let Some(table) = tables.pop() {
+            for bin in self.array_elements(table)? {
+                let mut node = *bin;
+                while node != 0 && seen.insert(node) {
+                    match self.type_name(node).as_str() {
+                        // a treeified bin of a ConcurrentHashMap, its nodes are still linked by next
+                        "java/util/concurrent/ConcurrentHashMap$TreeBin" => {
+                            node = self.reference_field(node, "first").unwrap_or(0);
+                            continue;
+                        }
+                        // the bin was already moved to the new table of a resize
+                        "java/util/concurrent/ConcurrentHashMap$ForwardingNode" => {
+                            if let Some(next_table) = self.reference_field(node, "nextTable") {
+                                if seen.insert(next_table) {
+                                    tables.push(next_table);
+                                }
+                            }
+                            break;
+                        }
+                        _ => {}
+                    }
+                    let key = self
+                        .reference_field(node, "key")
+                        // the keys of a WeakHashMap are the referents of its entries
+                        .or_else(|| self.reference_field(node, "referent"))
+                        .unwrap_or(0);
+                    let value = self
+                        .reference_field(node, "value")
+                        .or_else(|| self.reference_field(node, "val"))
+                        .unwrap_or(0);
+                    entries.push((key, value));
+                    node = self.reference_field(node, "next").unwrap_or(0);
+                }
+            }
+        }
+        Some(entries)
+    }
+
+    /// Formats a `java.util.UUID` in its canonical form, e.g. `123e4567-e89b-12d3-a456-426614174000`.
+    pub fn read_uuid(&self, object_id: U8) -> Option<String> {
+        let bits = |field: &str| match self.field_value(object_id, field) {
+            Some(Value::Long(bits)) => Some(*bits as u64),
+            _ => None,
+        };
+        let most = bits("mostSigBits")?;
+        let least = bits("leastSigBits")?;
+        Some(format!(
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            most >> 32,
+            (most >> 16) & 0xffff,
+            most & 0xffff,
+            least >> 48,
+            least & 0xffff_ffff_ffff
+        ))
+    }
+
+    /// The elements of an object array, including `null` elements.
+    pub fn array_elements(&self, object_id: U8) -> Option<&[U8]> {
+        match self.objects.get(&object_id).map(|r| &**r) {
+            Some(Reference::ObjectArray(array)) => Some(&array.values),
+            _ => None,
+        }
+    }
+
+    /// Decodes a list of strings, skipping elements that are not strings.
+    pub fn read_string_list(&self, object_id: U8) -> Option<Vec<String>> {
+        self.read_list(object_id).map(|elements| {
+            elements
+                .into_iter()
+                .filter_map(|element| self.read_string(element))
+                .collect()
+        })
+    }
+}
+
+/// The dominator tree of the object graph. An object dominates another object if every
+/// path from a GC root to the other object passes through it.
+pub struct DominatorTree {
+    /// the objects immediately dominated by each object
+    pub dominated: HashMap<U8, Vec<U8>>,
+    /// the immediate dominator of each reachable object
+    pub immediate_dominators: HashMap<U8, U8>,
+    pub retained_sizes: HashMap<U8, u64>,
+}
+
+/// Adds a reference to the graph. Null references are ignored. The graph only keeps
+/// one edge between two objects, so if there already is one, the stronger reference wins.
+/// [HeapDump::describe_edges] still finds all of them.
+fn add_reference(graph: &mut DiGraphMap<U8, Edge>, from: U8, to: U8, edge: Edge) {
+    if to == 0 {
+        return;
+    }
+    if let Some(existing) = graph.edge_weight_mut(from, to) {
+        if edge.kind < existing.kind {
+            *existing = edge;
+        }
+    } else {
+        graph.add_edge(from, to, edge);
+    }
+}
+
+/// The object a field value refers to, `None` for primitives and null.
+fn reference_of(value: &Value) -> Option<U8> {
+    match value {
+        Value::Object { object_id } | Value::Array { object_id } if *object_id != 0 => {
+            Some(*object_id)
+        }
+        _ => None,
+    }
+}
+
+/// Formats a field value or array element, references are formatted as their id.
+pub fn format_primitive(value: &Value) -> String {
+    match value {
+        Value::Object { object_id } => format!("{:#x}", object_id),
+        Value::Array { object_id } => format!("{:#x}", object_id),
+        Value::Byte(v) => v.to_string(),
+        Value::Char(v) => match char::from_u32(u32::from(*v)) {
+            Some(c) => format!("'{}'", c.escape_debug()),
+            None => format!("\\u{:04x}", u32::from(*v)),
+        },
+        Value::Short(v) => v.to_string(),
+        Value::Float(v) => v.to_string(),
+        Value::Double(v) => v.to_string(),
+        Value::Int(v) => v.to_string(),
+        Value::Long(v) => v.to_string(),
+        Value::Boolean(v) => v.to_string(),
+    }
+}
+
+fn primitive_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Object { .. } => "java/lang/Object",
+        Value::Array { .. } => "java/lang/Object",
+        Value::Byte(_) => "byte",
+        Value::Char(_) => "char",
+        Value::Short(_) => "short",
+        Value::Float(_) => "float",
+        Value::Double(_) => "double",
+        Value::Int(_) => "int",
+        Value::Long(_) => "long",
+        Value::Boolean(_) => "boolean",
+    }
+}
+
+/// Finds all subclasses of `java.lang.ref.Reference` that have special GC semantics and
+/// maps them to their reference kind and the index of the `referent` field in their instances.
+fn referent_fields(
+    names: &HashMap<U8, String>,
+    classes: &HashMap<U8, AnalysisClassInfo>,
+) -> HashMap<U8, (ReferenceKind, usize)> {
+    let name_of = |class_info: &AnalysisClassInfo| {
+        names
+            .get(&class_info.class_name_id)
+            .map(|name| name.as_str())
+            .unwrap_or("")
+    };
+    let mut referent_fields = HashMap::new();
+    for class_info in classes.values() {
+        let Some(kind) = referent_kind(names, classes, class_info.class_object_id) else {
+            continue;
+        };
+        let referent_index = instance_field_layout(classes, class_info.class_object_id)
+            .iter()
+            .position(|field| {
+                names
+                    .get(&field.name_id)
+                    .is_some_and(|name| name == "referent")
+                    && classes.get(&field.declaring_class_object_id).is_some_and(
+                        |declaring_class| name_of(declaring_class) == "java/lang/ref/Reference",
+                    )
+            });
+        if let Some(referent_index) = referent_index {
+            referent_fields.insert(class_info.class_object_id, (kind, referent_index));
+        }
+    }
+    referent_fields
+}
+
+/// The kind of the reference the `referent` of a subclass of `java.lang.ref.Reference` holds,
+/// `None` for other classes.
+fn referent_kind(
+    names: &HashMap<U8, String>,
+    classes: &HashMap<U8, AnalysisClassInfo>,
+    class_object_id: U8,
+) -> Option<ReferenceKind> {
+    super_classes(classes, class_object_id).find_map(|super_class| {
+        match names
+            .get(&super_class.class_name_id)
+            .map(|name| name.as_str())
+            .unwrap_or("")
+        {
+            "java/lang/ref/SoftReference" => Some(ReferenceKind::Soft),
+            "java/lang/ref/WeakReference" => Some(ReferenceKind::Weak),
+            "java/lang/ref/FinalReference" => Some(ReferenceKind::Final),
+            "java/lang/ref/PhantomReference" => Some(ReferenceKind::Phantom),
+            _ => None,
+        }
+    })
+}
+
+/// Iterates over the given class and all of its superclasses, starting with the class itself.
+pub fn super_classes(
+    classes: &HashMap<U8, AnalysisClassInfo>,
+    class_object_id: U8,
+) -> impl Iterator<Item = &AnalysisClassInfo> {
+    std::iter::successors(classes.get(&class_object_id), |class_info| {
+        classes.get(&class_info.super_class_object_id)
+    })
+}
+
+/// The instance fields of a class in the order their values appear in an instance dump:
+/// the fields declared by the class itself first, followed by those of its superclasses.
+pub fn instance_field_layout(
+    classes: &HashMap<U8, AnalysisClassInfo>,
+    class_object_id: U8,
+) -> Vec<FieldSlot> {
+    super_classes(classes, class_object_id)
+        .flat_map(|class_info| {
+            class_info
+                .instance_field_name_ids
+                .iter()
+                .map(|&name_id| FieldSlot {
+                    declaring_class_object_id: class_info.class_object_id,
+                    name_id,
+                })
+        })
+        .collect()
 }
 
 impl PartialEq for HeapDump {
@@ -94,6 +1089,21 @@ pub struct AnalysisClassInfo {
     pub class_name_id: U8,
     pub super_class_object_id: U8,
     pub class_loader_object_id: U8,
+    /// names of the instance fields declared by this class (not its superclasses)
+    pub instance_field_name_ids: Vec<U8>,
+    pub static_fields: Vec<StaticField>,
+}
+
+pub struct StaticField {
+    pub name_id: U8,
+    pub value: Value,
+}
+
+/// An instance field together with the class that declares it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FieldSlot {
+    pub declaring_class_object_id: U8,
+    pub name_id: U8,
 }
 
 #[derive(Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -106,6 +1116,47 @@ pub enum Reference {
     FakeCommonRoot,
 }
 
+impl Reference {
+    pub fn object_id(&self) -> U8 {
+        match self {
+            Reference::Instance(instance) => instance.object_id,
+            Reference::ObjectArray(array) => array.object_id,
+            Reference::PrimitiveArray(array) => array.object_id,
+            Reference::FakeCommonRoot => FAKE_ROOT_ID,
+        }
+    }
+
+    pub fn shallow_size(&self) -> u64 {
+        match self {
+            Reference::Instance(instance) => {
+                OBJECT_HEADER_SIZE + instance.fields.iter().map(value_size).sum::<u64>()
+            }
+            Reference::ObjectArray(array) => {
+                ARRAY_HEADER_SIZE + ID_SIZE * array.values.len() as u64
+            }
+            Reference::PrimitiveArray(array) => {
+                ARRAY_HEADER_SIZE + array.values.iter().map(value_size).sum::<u64>()
+            }
+            Reference::FakeCommonRoot => 0,
+        }
+    }
+}
+
+fn value_size(value: &Value) -> u64 {
+    match value {
+        Value::Object { .. } => ID_SIZE,
+        Value::Array { .. } => ID_SIZE,
+        Value::Byte(_) => 1,
+        Value::Char(_) => 2,
+        Value::Short(_) => 2,
+        Value::Float(_) => 4,
+        Value::Double(_) => 8,
+        Value::Int(_) => 4,
+        Value::Long(_) => 8,
+        Value::Boolean(_) => 1,
+    }
+}
+
 pub struct InstanceInfo {
     pub class_object_id: U8,
     pub object_id: U8,
@@ -219,16 +1270,20 @@ pub fn from_reader<T: Read + Seek>(mut reader: HprofReader<T>) -> HeapDump {
                         HeapDumpTag::HprofGcRootUnknown => {}
                         HeapDumpTag::HprofGcRootThreadObj {
                             thread_object_id, ..
-                        } => roots.push(thread_object_id),
+                        } => roots.push((thread_object_id, RootKind::ThreadObject)),
                         HeapDumpTag::HprofGcRootJniGlobal { object_id, .. } => {
-                            roots.push(object_id)
+                            roots.push((object_id, RootKind::JniGlobal))
+                        }
+                        HeapDumpTag::HprofGcRootJniLocal { object_id, .. } => {
+                            roots.push((object_id, RootKind::JniLocal))
                         }
-                        HeapDumpTag::HprofGcRootJniLocal { object_id, .. } => roots.push(object_id),
                         HeapDumpTag::HprofGcRootJavaFrame { object_id, .. } => {
-                            roots.push(object_id)
+                            roots.push((object_id, RootKind::JavaFrame))
                         }
                         HeapDumpTag::HprofGcRootNativeStack => {}
-                        HeapDumpTag::HprofGcRootStickyClass { object_id } => roots.push(object_id),
+                        HeapDumpTag::HprofGcRootStickyClass { object_id } => {
+                            roots.push((object_id, RootKind::StickyClass))
+                        }
                         HeapDumpTag::HprofGcRootThreadBlock => {}
                         HeapDumpTag::HprofGcRootMonitorUsed => {}
                         HeapDumpTag::HprofGcClassDump(class_info) => {
@@ -239,6 +1294,19 @@ pub fn from_reader<T: Read + Seek>(mut reader: HprofReader<T>) -> HeapDump {
                                     .unwrap(),
                                 super_class_object_id: class_info.super_class_object_id,
                                 class_loader_object_id: class_info.class_loader_object_id,
+                                instance_field_name_ids: class_info
+                                    .instance_fields
+                                    .iter()
+                                    .map(|field| field.name_id)
+                                    .collect(),
+                                static_fields: class_info
+                                    .static_fields
+                                    .into_iter()
+                                    .map(|field| StaticField {
+                                        name_id: field.name_id,
+                                        value: field.value,
+                                    })
+                                    .collect(),
                             };
                             classes.insert(class_info.class_object_id, ci);
                         }
@@ -311,3 +1379,728 @@ pub fn from_reader<T: Read + Seek>(mut reader: HprofReader<T>) -> HeapDump {
         roots,
     )
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Assembles a small heap dump for tests. Ids are handed out in ascending order.
+    pub(crate) struct HeapDumpBuilder {
+        next_id: U8,
+        names: HashMap<U8, String>,
+        classes: HashMap<U8, AnalysisClassInfo>,
+        objects: HashMap<U8, Reference>,
+        roots: Vec<(U8, RootKind)>,
+        string_class: Option<U8>,
+        list_classes: Option<(U8, U8)>,
+        map_classes: Option<(U8, U8, U8)>,
+    }
+
+    impl HeapDumpBuilder {
+        pub(crate) fn new() -> HeapDumpBuilder {
+            HeapDumpBuilder {
+                next_id: 0x1000,
+                names: HashMap::new(),
+                classes: HashMap::new(),
+                objects: HashMap::new(),
+                roots: Vec::new(),
+                string_class: None,
+                list_classes: None,
+                map_classes: None,
+            }
+        }
+
+        fn next_id(&mut self) -> U8 {
+            self.next_id += 8;
+            self.next_id
+        }
+
+        fn name(&mut self, name: &str) -> U8 {
+            let id = self.next_id();
+            self.names.insert(id, name.to_string());
+            id
+        }
+
+        /// Adds a class like `java/lang/String` declaring the given instance fields.
+        pub(crate) fn class(
+            &mut self,
+            name: &str,
+            super_class_object_id: U8,
+            class_loader_object_id: U8,
+            fields: &[&str],
+        ) -> U8 {
+            let class_object_id = self.next_id();
+            let class_name_id = self.name(name);
+            let instance_field_name_ids = fields.iter().map(|field| self.name(field)).collect();
+            self.classes.insert(
+                class_object_id,
+                AnalysisClassInfo {
+                    class_object_id,
+                    class_name_id,
+                    super_class_object_id,
+                    class_loader_object_id,
+                    instance_field_name_ids,
+                    static_fields: Vec::new(),
+                },
+            );
+            class_object_id
+        }
+
+        pub(crate) fn static_field(&mut self, class_object_id: U8, name: &str, value: Value) {
+            let name_id = self.name(name);
+            self.classes
+                .get_mut(&class_object_id)
+                .expect("unknown class")
+                .static_fields
+                .push(StaticField { name_id, value });
+        }
+
+        /// Adds an instance, the field values are in dump order: own fields first.
+        pub(crate) fn instance(&mut self, class_object_id: U8, fields: Vec<Value>) -> U8 {
+            let object_id = self.next_id();
+            let instance = InstanceInfo {
+                class_object_id,
+                object_id,
+                fields,
+            };
+            self.objects
+                .insert(object_id, Reference::Instance(instance));
+            object_id
+        }
+
+        /// Changes a field value of an instance, e.g. to create cycles.
+        pub(crate) fn set_field(&mut self, object_id: U8, index: usize, value: Value) {
+            let Some(Reference::Instance(instance)) = self.objects.get_mut(&object_id) else {
+                panic!("unknown instance");
+            };
+            instance.fields[index] = value;
+        }
+
+        pub(crate) fn object_array(&mut self, class_object_id: U8, values: Vec<U8>) -> U8 {
+            let object_id = self.next_id();
+            let array = ObjectArray {
+                class_object_id,
+                object_id,
+                values,
+            };
+            self.objects
+                .insert(object_id, Reference::ObjectArray(array));
+            object_id
+        }
+
+        pub(crate) fn primitive_array(&mut self, values: Vec<Value>) -> U8 {
+            let object_id = self.next_id();
+            let array = PrimitiveArray { object_id, values };
+            self.objects
+                .insert(object_id, Reference::PrimitiveArray(array));
+            object_id
+        }
+
+        /// Adds a compact Latin-1 `java/lang/String` like the ones of Java 9+.
+        pub(crate) fn string(&mut self, value: &str) -> U8 {
+            let string_class = match self.string_class {
+                Some(class_object_id) => class_object_id,
+                None => {
+                    let class_object_id = self.class("java/lang/String", 0, 0, &["value", "coder"]);
+                    self.string_class = Some(class_object_id);
+                    class_object_id
+                }
+            };
+            let bytes = value.chars().map(|c| Value::Byte(c as u8 as i8)).collect();
+            let array = self.primitive_array(bytes);
+            self.instance(string_class, vec![object(array), Value::Byte(0)])
+        }
+
+        /// Adds a `java/util/ArrayList` with the given elements.
+        pub(crate) fn list(&mut self, elements: Vec<U8>) -> U8 {
+            let (list_class, array_class) = match self.list_classes {
+                Some(classes) => classes,
+                None => {
+                    let list_class =
+                        self.class("java/util/ArrayList", 0, 0, &["elementData", "size"]);
+                    let array_class = self.class("[Ljava/lang/Object;", 0, 0, &[]);
+                    self.list_classes = Some((list_class, array_class));
+                    (list_class, array_class)
+                }
+            };
+            let size = Value::Int(elements.len() as i32);
+            let array = self.object_array(array_class, elements);
+            self.instance(list_class, vec![object(array), size])
+        }
+
+        /// Adds a `java/util/HashMap` with all entries in a single bin, in the given order.
+        pub(crate) fn hash_map(&mut self, entries: Vec<(U8, U8)>) -> U8 {
+            let (map_class, node_class, table_class) = match self.map_classes {
+                Some(classes) => classes,
+                None => {
+                    let map_class = self.class("java/util/HashMap", 0, 0, &["table"]);
+                    let node_class =
+                        self.class("java/util/HashMap$Node", 0, 0, &["key", "value", "next"]);
+                    let table_class = self.class("[Ljava/util/HashMap$Node;", 0, 0, &[]);
+                    self.map_classes = Some((map_class, node_class, table_class));
+                    (map_class, node_class, table_class)
+                }
+            };
+            let mut next = 0;
+            for (key, value) in entries.into_iter().rev() {
+                next = self.instance(node_class, vec![object(key), object(value), object(next)]);
+            }
+            let table = self.object_array(table_class, vec![next]);
+            self.instance(map_class, vec![object(table)])
+        }
+
+        pub(crate) fn root(&mut self, object_id: U8) {
+            self.roots.push((object_id, RootKind::JniGlobal));
+        }
+
+        pub(crate) fn build(self) -> HeapDump {
+            let mut objects = HashMap::new();
+            let mut objects_by_class = MultiMap::new();
+            for (object_id, reference) in self.objects {
+                let reference = Rc::new(reference);
+                match &*reference {
+                    Reference::Instance(instance) => {
+                        objects_by_class.insert(instance.class_object_id, reference.clone())
+                    }
+                    Reference::ObjectArray(array) => {
+                        objects_by_class.insert(array.class_object_id, reference.clone())
+                    }
+                    _ => {}
+                }
+                objects.insert(object_id, reference);
+            }
+            HeapDump::new(
+                Utc::now(),
+                self.names,
+                self.classes,
+                objects,
+                objects_by_class,
+                self.roots,
+            )
+        }
+    }
+
+    pub(crate) fn object(object_id: U8) -> Value {
+        Value::Object { object_id }
+    }
+
+    #[test]
+    fn add_reference_keeps_the_strongest_edge() {
+        let referent = Edge {
+            kind: ReferenceKind::Weak,
+            origin: EdgeOrigin::InstanceField(FieldSlot {
+                declaring_class_object_id: 1,
+                name_id: 2,
+            }),
+        };
+        let field = Edge::strong(EdgeOrigin::InstanceField(FieldSlot {
+            declaring_class_object_id: 3,
+            name_id: 4,
+        }));
+        let class = Edge::strong(EdgeOrigin::Class);
+
+        let mut graph = DiGraphMap::new();
+        add_reference(&mut graph, 10, 20, referent);
+        add_reference(&mut graph, 10, 20, field);
+        add_reference(&mut graph, 10, 20, class);
+        // the first strong edge wins over the weak one and is not replaced by a later strong one
+        assert_eq!(graph.edge_weight(10, 20), Some(&field));
+
+        let mut graph = DiGraphMap::new();
+        add_reference(&mut graph, 10, 20, field);
+        add_reference(&mut graph, 10, 20, referent);
+        assert_eq!(graph.edge_weight(10, 20), Some(&field));
+    }
+
+    #[test]
+    fn all_references_between_two_objects_are_described() {
+        let mut builder = HeapDumpBuilder::new();
+        let reference_class = builder.class("java/lang/ref/Reference", 0, 0, &["referent"]);
+        let weak_class = builder.class("java/lang/ref/WeakReference", reference_class, 0, &[]);
+        let entry_class = builder.class("example/Entry", weak_class, 0, &["value"]);
+        let holder_class = builder.class("example/Holder", 0, 0, &["type"]);
+        let node_class = builder.class("java/util/HashMap$Node", 0, 0, &["key", "value"]);
+        let target = builder.instance(holder_class, vec![object(0)]);
+        // a field that refers to the class of the object itself
+        builder.set_field(target, 0, object(holder_class));
+        let entry = builder.instance(entry_class, vec![object(target), object(target)]);
+        // a map entry whose key is its value
+        let node = builder.instance(node_class, vec![object(target), object(target)]);
+        builder.root(entry);
+        builder.root(node);
+        let heap_dump = builder.build();
+
+        let describe = |from, to| {
+            let edge = heap_dump.object_graph.edge_weight(from, to).unwrap();
+            heap_dump.describe_edges(from, to, edge)
+        };
+        assert_eq!(
+            describe(node, target),
+            "java/util/HashMap$Node.key, java/util/HashMap$Node.value"
+        );
+        assert_eq!(
+            describe(entry, target),
+            "example/Entry.value, java/lang/ref/Reference.referent (weak)"
+        );
+        assert_eq!(
+            describe(target, holder_class),
+            "<class>, example/Holder.type"
+        );
+        // only the strongest reference is kept in the graph
+        assert_eq!(
+            heap_dump
+                .object_graph
+                .edge_weight(entry, target)
+                .unwrap()
+                .kind,
+            ReferenceKind::Strong
+        );
+    }
+
+    #[test]
+    fn only_strong_references_lead_to_an_object_reachable_through_a_weak_referent() {
+        let mut builder = HeapDumpBuilder::new();
+        let reference_class = builder.class("java/lang/ref/Reference", 0, 0, &["referent"]);
+        let weak_class = builder.class("java/lang/ref/WeakReference", reference_class, 0, &[]);
+        let holder_class = builder.class("example/Holder", 0, 0, &["next"]);
+        let target = builder.instance(holder_class, vec![object(0)]);
+        let weak = builder.instance(weak_class, vec![object(target)]);
+        let inner = builder.instance(holder_class, vec![object(target)]);
+        let outer = builder.instance(holder_class, vec![object(inner)]);
+        builder.root(weak);
+        builder.root(outer);
+        let heap_dump = builder.build();
+
+        assert_eq!(
+            heap_dump
+                .object_graph
+                .edge_weight(weak, target)
+                .unwrap()
+                .kind,
+            ReferenceKind::Weak
+        );
+        // the weak reference is the shorter path, but it does not keep the target alive
+        assert_eq!(
+            heap_dump.path_to_root(target, ReferenceFilter::All),
+            Some(vec![weak, target])
+        );
+        assert_eq!(
+            heap_dump.path_to_root(target, ReferenceFilter::StrongOnly),
+            Some(vec![outer, inner, target])
+        );
+        assert_eq!(
+            heap_dump
+                .dominator_tree(ReferenceFilter::StrongOnly)
+                .immediate_dominators[&target],
+            inner
+        );
+    }
+
+    #[test]
+    fn well_known_objects_are_previewed() {
+        let mut builder = HeapDumpBuilder::new();
+        let integer_class = builder.class("java/lang/Integer", 0, 0, &["value"]);
+        let boolean_class = builder.class("java/lang/Boolean", 0, 0, &["value"]);
+        let character_class = builder.class("java/lang/Character", 0, 0, &["value"]);
+        let enum_class = builder.class("java/lang/Enum", 0, 0, &["name", "ordinal"]);
+        let mode_class = builder.class("example/Mode", enum_class, 0, &[]);
+        let uuid_class = builder.class("java/util/UUID", 0, 0, &["mostSigBits", "leastSigBits"]);
+        let array_class = builder.class("[Ljava/lang/Object;", 0, 0, &[]);
+        let other_class = builder.class("example/Other", 0, 0, &[]);
+
+        let integer = builder.instance(integer_class, vec![Value::Int(-42)]);
+        let boolean = builder.instance(boolean_class, vec![Value::Boolean(true)]);
+        let character = builder.instance(character_class, vec![Value::Char(u16::from(b'x'))]);
+        let name = builder.string("SURVIVAL");
+        let mode = builder.instance(mode_class, vec![object(name), Value::Int(0)]);
+        let uuid = builder.instance(
+            uuid_class,
+            vec![
+                Value::Long(0x0123_4567_89ab_cdef),
+                Value::Long(0xfedc_ba98_7654_3210_u64 as i64),
+            ],
+        );
+        let short = builder.string("short \"text\"");
+        let long = builder.string(&"a".repeat(MAX_PREVIEW_LENGTH + 1));
+        let exact = builder.string(&"b".repeat(MAX_PREVIEW_LENGTH));
+        let object_array = builder.object_array(array_class, vec![integer]);
+        let primitive_array = builder.primitive_array(vec![Value::Int(1)]);
+        let other = builder.instance(other_class, vec![]);
+        let heap_dump = builder.build();
+
+        let cases = [
+            (integer, Some("-42".to_string())),
+            (boolean, Some("true".to_string())),
+            (character, Some("'x'".to_string())),
+            (mode, Some("SURVIVAL".to_string())),
+            (mode_class, Some("example/Mode".to_string())),
+            (
+                uuid,
+                Some("01234567-89ab-cdef-fedc-ba9876543210".to_string()),
+            ),
+            (short, Some("\"short \\\"text\\\"\"".to_string())),
+            (
+                long,
+                Some(format!("\"{}…\"", "a".repeat(MAX_PREVIEW_LENGTH))),
+            ),
+            (exact, Some(format!("{:?}", "b".repeat(MAX_PREVIEW_LENGTH)))),
+            (object_array, None),
+            (primitive_array, None),
+            (other, None),
+        ];
+        for (object_id, expected) in cases {
+            assert_eq!(heap_dump.preview(object_id), expected, "{:#x}", object_id);
+        }
+    }
+
+    #[test]
+    fn add_reference_ignores_null() {
+        let mut graph = DiGraphMap::new();
+        add_reference(&mut graph, 10, 0, Edge::strong(EdgeOrigin::Class));
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn references_beyond_the_field_layout_are_kept() {
+        let mut builder = HeapDumpBuilder::new();
+        // the superclass declaring the second field is missing from the dump
+        let class = builder.class("Holder", 0x10, 0, &["first"]);
+        let target = builder.class("Target", 0, 0, &[]);
+        let first = builder.instance(target, vec![]);
+        let second = builder.instance(target, vec![]);
+        let holder = builder.instance(class, vec![object(first), object(second)]);
+        builder.root(holder);
+        let heap_dump = builder.build();
+
+        let describe = |to| {
+            let edge = heap_dump.object_graph.edge_weight(holder, to).unwrap();
+            heap_dump.describe_edge(edge)
+        };
+        assert_eq!(describe(first), "Holder.first");
+        assert_eq!(describe(second), "<field #1>");
+        assert_eq!(heap_dump.field_value(holder, "first"), Some(&object(first)));
+        assert_eq!(heap_dump.field_layout(class).len(), 1);
+    }
+
+    /// Two roots: `n1 -> n2 -> leaf1`, `n1 -> leaf2` and `n3 -> leaf3`, where `n2` also references `leaf3`.
+    fn nodes_and_leaves() -> (HeapDump, [U8; 6]) {
+        let mut builder = HeapDumpBuilder::new();
+        let node = builder.class("Node", 0, 0, &["left", "right"]);
+        let leaf = builder.class("Leaf", 0, 0, &[]);
+        let leaf1 = builder.instance(leaf, vec![]);
+        let leaf2 = builder.instance(leaf, vec![]);
+        let leaf3 = builder.instance(leaf, vec![]);
+        let n2 = builder.instance(node, vec![object(leaf1), object(leaf3)]);
+        let n1 = builder.instance(node, vec![object(n2), object(leaf2)]);
+        let n3 = builder.instance(node, vec![object(leaf3), object(0)]);
+        builder.root(n1);
+        builder.root(n3);
+        (builder.build(), [n1, n2, n3, leaf1, leaf2, leaf3])
+    }
+
+    #[test]
+    fn retained_sizes_follow_the_dominator_tree() {
+        let (heap_dump, [n1, n2, n3, leaf1, _, leaf3]) = nodes_and_leaves();
+        let tree = heap_dump.dominator_tree(ReferenceFilter::All);
+        assert_eq!(tree.immediate_dominators[&leaf1], n2);
+        // reachable from both roots, so only the fake root dominates it
+        assert_eq!(tree.immediate_dominators[&leaf3], FAKE_ROOT_ID);
+
+        let retained_size = |object_id| heap_dump.retained_size(object_id, ReferenceFilter::All);
+        assert_eq!(retained_size(leaf1), Some(16));
+        assert_eq!(retained_size(n2), Some(32 + 16));
+        assert_eq!(retained_size(n1), Some(32 + 48 + 16));
+        assert_eq!(retained_size(n3), Some(32));
+        assert_eq!(retained_size(0x10), None);
+    }
+
+    #[test]
+    fn retained_size_by_group_counts_nested_objects_once() {
+        let (heap_dump, [n1, ..]) = nodes_and_leaves();
+        let sizes = heap_dump.retained_size_by_group(ReferenceFilter::All, |object_id| {
+            heap_dump
+                .class_of(object_id)
+                .map(|class| heap_dump.class_name(class))
+        });
+        // n2 is part of the retained size of n1 and must not be added again
+        assert_eq!(sizes["Node"], 96 + 32);
+        assert_eq!(sizes["Leaf"], 3 * 16);
+
+        let sizes = heap_dump.retained_size_by_group(ReferenceFilter::All, |object_id| {
+            (object_id == n1).then_some(())
+        });
+        assert_eq!(sizes[&()], 96);
+    }
+
+    #[test]
+    fn read_uuid_formats_the_canonical_form() {
+        let mut builder = HeapDumpBuilder::new();
+        let uuid_class = builder.class("java/util/UUID", 0, 0, &["mostSigBits", "leastSigBits"]);
+        let uuid = builder.instance(
+            uuid_class,
+            vec![
+                Value::Long(0x123e_4567_e89b_12d3),
+                Value::Long(0xa456_4266_1417_4000_u64 as i64),
+            ],
+        );
+        // leading zeros are kept
+        let small = builder.instance(uuid_class, vec![Value::Long(1), Value::Long(2)]);
+        let not_a_uuid = builder.instance(uuid_class, vec![Value::Int(1), Value::Long(2)]);
+        let heap_dump = builder.build();
+
+        assert_eq!(
+            heap_dump.read_uuid(uuid).as_deref(),
+            Some("123e4567-e89b-12d3-a456-426614174000")
+        );
+        assert_eq!(
+            heap_dump.read_uuid(small).as_deref(),
+            Some("00000000-0000-0001-0000-000000000002")
+        );
+        assert_eq!(heap_dump.read_uuid(not_a_uuid), None);
+    }
+
+    #[test]
+    fn read_string_decodes_latin1_and_utf16() {
+        let mut builder = HeapDumpBuilder::new();
+        let latin1 = builder.string("Grüße");
+        // "€1" in little endian UTF-16
+        let bytes = [0xac, 0x20, 0x31, 0x00]
+            .into_iter()
+            .map(|byte: u8| Value::Byte(byte as i8))
+            .collect();
+        let array = builder.primitive_array(bytes);
+        let utf16 = builder.string("");
+        builder.set_field(utf16, 0, object(array));
+        builder.set_field(utf16, 1, Value::Byte(1));
+        let empty = builder.string("");
+        let heap_dump = builder.build();
+
+        assert_eq!(heap_dump.read_string(latin1).as_deref(), Some("Grüße"));
+        assert_eq!(heap_dump.read_string(utf16).as_deref(), Some("€1"));
+        assert_eq!(heap_dump.read_string(empty).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn read_string_honours_the_offset_of_old_strings() {
+        let mut builder = HeapDumpBuilder::new();
+        let string_class = builder.class(
+            "java/lang/String",
+            0,
+            0,
+            &["value", "offset", "count", "hash"],
+        );
+        let chars = "key=value"
+            .encode_utf16()
+            .map(Value::Char)
+            .collect::<Vec<_>>();
+        let array = builder.primitive_array(chars);
+        let string = |builder: &mut HeapDumpBuilder, offset: i32, count: i32| {
+            builder.instance(
+                string_class,
+                vec![
+                    object(array),
+                    Value::Int(offset),
+                    Value::Int(count),
+                    Value::Int(0),
+                ],
+            )
+        };
+        let whole = string(&mut builder, 0, 9);
+        let key = string(&mut builder, 0, 3);
+        let value = string(&mut builder, 4, 5);
+        let too_long = string(&mut builder, 4, 6);
+        let negative = string(&mut builder, -1, 3);
+        let beyond = string(&mut builder, 10, 1);
+        let heap_dump = builder.build();
+
+        assert_eq!(heap_dump.read_string(whole).as_deref(), Some("key=value"));
+        assert_eq!(heap_dump.read_string(key).as_deref(), Some("key"));
+        assert_eq!(heap_dump.read_string(value).as_deref(), Some("value"));
+        // the count is clamped, an offset outside of the array falls back to the whole value
+        assert_eq!(heap_dump.read_string(too_long).as_deref(), Some("value"));
+        assert_eq!(
+            heap_dump.read_string(negative).as_deref(),
+            Some("key=value")
+        );
+        assert_eq!(heap_dump.read_string(beyond).as_deref(), Some("key=value"));
+    }
+
+    #[test]
+    fn read_string_applies_the_offset_to_byte_arrays() {
+        let mut builder = HeapDumpBuilder::new();
+        let string_class = builder.class(
+            "java/lang/String",
+            0,
+            0,
+            &["value", "coder", "offset", "count"],
+        );
+        let latin1 = builder.primitive_array(
+            "key=value"
+                .bytes()
+                .map(|byte| Value::Byte(byte as i8))
+                .collect(),
+        );
+        let utf16 = builder.primitive_array(
+            "key=välue"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes)
+                .map(|byte| Value::Byte(byte as i8))
+                .collect(),
+        );
+        let string = |builder: &mut HeapDumpBuilder, array, coder, offset, count| {
+            builder.instance(
+                string_class,
+                vec![
+                    object(array),
+                    Value::Byte(coder),
+                    Value::Int(offset),
+                    Value::Int(count),
+                ],
+            )
+        };
+        let latin1_value = string(&mut builder, latin1, 0, 4, 5);
+        let utf16_value = string(&mut builder, utf16, 1, 4, 5);
+        let utf16_too_long = string(&mut builder, utf16, 1, 4, 10);
+        let heap_dump = builder.build();
+
+        assert_eq!(
+            heap_dump.read_string(latin1_value).as_deref(),
+            Some("value")
+        );
+        assert_eq!(heap_dump.read_string(utf16_value).as_deref(), Some("välue"));
+        assert_eq!(
+            heap_dump.read_string(utf16_too_long).as_deref(),
+            Some("välue")
+        );
+    }
+
+    #[test]
+    fn read_map_follows_chains_once() {
+        let mut builder = HeapDumpBuilder::new();
+        let key_class = builder.class("example/Key", 0, 0, &[]);
+        let [a, b, c, d] = [(); 4].map(|_| builder.instance(key_class, vec![]));
+        let map = builder.hash_map(vec![(a, b), (c, d)]);
+        let linked_map_class = builder.class("java/util/LinkedHashMap", 0, 0, &["table"]);
+        let node_class = builder.class("java/util/HashMap$Node", 0, 0, &["key", "value", "next"]);
+        let table_class = builder.class("[Ljava/util/HashMap$Node;", 0, 0, &[]);
+        // two bins, the second one links back to its first node
+        let first = builder.instance(node_class, vec![object(a), object(0), object(0)]);
+        let second = builder.instance(node_class, vec![object(b), object(c), object(0)]);
+        let third = builder.instance(node_class, vec![object(c), object(0), object(second)]);
+        builder.set_field(second, 2, object(third));
+        let table = builder.object_array(table_class, vec![first, 0, second]);
+        let cyclic_map = builder.instance(linked_map_class, vec![object(table)]);
+        let empty_map = builder.instance(linked_map_class, vec![object(0)]);
+        let heap_dump = builder.build();
+
+        assert_eq!(heap_dump.read_map(map), Some(vec![(a, b), (c, d)]));
+        assert_eq!(
+            heap_dump.read_map(cyclic_map),
+            Some(vec![(a, 0), (b, c), (c, 0)])
+        );
+        assert_eq!(heap_dump.read_map(empty_map), Some(vec![]));
+        assert_eq!(heap_dump.read_map(a), None);
+    }
+
+    #[test]
+    fn read_map_reads_tree_bins_and_resized_tables() {
+        let mut builder = HeapDumpBuilder::new();
+        let key_class = builder.class("example/Key", 0, 0, &[]);
+        let [a, b, c] = [(); 3].map(|_| builder.instance(key_class, vec![]));
+        let map_class = builder.class("java/util/concurrent/ConcurrentHashMap", 0, 0, &["table"]);
+        let node_class = builder.class(
+            "java/util/concurrent/ConcurrentHashMap$Node",
+            0,
+            0,
+            &["key", "val", "next"],
+        );
+        let tree_bin_class = builder.class(
+            "java/util/concurrent/ConcurrentHashMap$TreeBin",
+            0,
+            0,
+            &["first"],
+        );
+        let forwarding_class = builder.class(
+            "java/util/concurrent/ConcurrentHashMap$ForwardingNode",
+            0,
+            0,
+            &["nextTable"],
+        );
+        let table_class =
+            builder.class("[Ljava/util/concurrent/ConcurrentHashMap$Node;", 0, 0, &[]);
+        let tree_second = builder.instance(node_class, vec![object(b), object(a), object(0)]);
+        let tree_first =
+            builder.instance(node_class, vec![object(a), object(b), object(tree_second)]);
+        let tree_bin = builder.instance(tree_bin_class, vec![object(tree_first)]);
+        // the bin of c was moved to the new table, both bins point to it
+        let moved = builder.instance(node_class, vec![object(c), object(a), object(0)]);
+        let next_table = builder.object_array(table_class, vec![0, moved, 0, 0]);
+        let forwarding = builder.instance(forwarding_class, vec![object(next_table)]);
+        let table = builder.object_array(table_class, vec![tree_bin, forwarding]);
+        let map = builder.instance(map_class, vec![object(table)]);
+        let forwarding_too = builder.instance(forwarding_class, vec![object(next_table)]);
+        let table = builder.object_array(table_class, vec![forwarding, forwarding_too]);
+        let resized_map = builder.instance(map_class, vec![object(table)]);
+        let heap_dump = builder.build();
+
+        assert_eq!(heap_dump.read_map(map), Some(vec![(a, b), (b, a), (c, a)]));
+        assert_eq!(heap_dump.read_map(resized_map), Some(vec![(c, a)]));
+    }
+
+    #[test]
+    fn read_map_uses_the_referents_of_weak_hash_maps_as_keys() {
+        let mut builder = HeapDumpBuilder::new();
+        let key_class = builder.class("example/Key", 0, 0, &[]);
+        let [a, b] = [(); 2].map(|_| builder.instance(key_class, vec![]));
+        let reference_class = builder.class(
+            "java/lang/ref/Reference",
+            0,
+            0,
+            &["referent", "queue", "next"],
+        );
+        let weak_reference_class =
+            builder.class("java/lang/ref/WeakReference", reference_class, 0, &[]);
+        let entry_class = builder.class(
+            "java/util/WeakHashMap$Entry",
+            weak_reference_class,
+            0,
+            &["value", "next"],
+        );
+        let map_class = builder.class("java/util/WeakHashMap", 0, 0, &["table"]);
+        let table_class = builder.class("[Ljava/util/WeakHashMap$Entry;", 0, 0, &[]);
+        // own fields first, then the ones of Reference, whose next must not be followed
+        let second = builder.instance(
+            entry_class,
+            vec![object(a), object(0), object(b), object(0), object(a)],
+        );
+        let first = builder.instance(
+            entry_class,
+            vec![object(b), object(second), object(a), object(0), object(b)],
+        );
+        let table = builder.object_array(table_class, vec![first]);
+        let map = builder.instance(map_class, vec![object(table)]);
+        let heap_dump = builder.build();
+
+        assert_eq!(heap_dump.read_map(map), Some(vec![(a, b), (b, a)]));
+    }
+
+    #[test]
+    fn read_list_skips_the_placeholder_of_list12() {
+        let mut builder = HeapDumpBuilder::new();
+        let object_class = builder.class("java/lang/Object", 0, 0, &[]);
+        let key_class = builder.class("example/Key", 0, 0, &[]);
+        let [a, b] = [(); 2].map(|_| builder.instance(key_class, vec![]));
+        let list12_class =
+            builder.class("java/util/ImmutableCollections$List12", 0, 0, &["e0", "e1"]);
+        let empty = builder.instance(object_class, vec![]);
+        let single = builder.instance(list12_class, vec![object(a), object(empty)]);
+        let pair = builder.instance(list12_class, vec![object(a), object(b)]);
+        let list = builder.list(vec![b, a]);
+        let heap_dump = builder.build();
+
+        assert_eq!(heap_dump.read_list(single), Some(vec![a]));
+        assert_eq!(heap_dump.read_list(pair), Some(vec![a, b]));
+        assert_eq!(heap_dump.read_list(list), Some(vec![b, a]));
+        assert_eq!(heap_dump.read_list(a), None);
+    }
+}
@@ -1,17 +1,20 @@
 use crate::heap_dump::from_reader;
-use crate::AppRoute;
+use crate::view_heap_dump::AnalysisRoutes;
 use hprof_rs::reader::HprofReader;
 use patternfly_yew::prelude::{
     use_backdrop, Bullseye, Button, ButtonVariant, FileUpload, FileUploadDetails, FileUploadSelect,
     Form, FormGroup, HelperText, HelperTextItem, HelperTextItemVariant, InputGroup, Modal,
     ModalVariant, TextInput,
 };
+use std::collections::HashMap;
 use std::io::Cursor;
+use std::rc::Rc;
 use web_sys::js_sys::{ArrayBuffer, Uint8Array};
 use yew::{function_component, html, use_callback, use_node_ref, use_state, Callback, Html};
 use yew_hooks::{use_drop_with_options, UseDropOptions};
 use yew_more_hooks::hooks::r#async::*;
-use yew_router::hooks::use_navigator;
+use yew_router::hooks::{use_location, use_navigator};
+use yew_router::Routable;
 
 #[derive(Clone, Debug, PartialEq)]
 enum DropContent {
@@ -117,6 +120,15 @@ pub(crate) fn upload() -> Html {
     let backdrop = use_backdrop();
 
     let navigator = use_navigator().unwrap();
+    // the analysis page to show after loading, e.g. when opening a deep link
+    let target = use_location()
+        .and_then(|location| location.query::<HashMap<String, String>>().ok())
+        .and_then(|query| {
+            query
+                .get("target")
+                .and_then(|path| AnalysisRoutes::recognize(path))
+        })
+        .unwrap_or_default();
 
     let onsubmit = {
         let processing = processing.clone();
@@ -129,7 +141,7 @@ pub(crate) fn upload() -> Html {
                 match heap_dump {
                     Ok(heap_dump) => {
                         backdrop.close();
-                        navigator.push_with_state(&AppRoute::Analysis, heap_dump)
+                        navigator.push_with_state(&target, Rc::new(heap_dump))
                     }
                     Err(err) => backdrop.open(html!(
                         <Bullseye plain=true>
@@ -1,3 +1,4 @@
+mod analysis;
 mod load_file;
 mod view_heap_dump;
 mod heap_dump;
@@ -28,12 +29,14 @@ enum AppRoute {
     Upload,
     #[at("/view")]
     Analysis,
+    #[at("/view/*")]
+    AnalysisDetails,
 }
 
 fn route(target: AppRoute) -> Html {
     match target {
         AppRoute::Upload => html!(<UploadFile/>),
-        AppRoute::Analysis => html!(<ViewHeapDump/>),
+        AppRoute::Analysis | AppRoute::AnalysisDetails => html!(<ViewHeapDump/>),
     }
 }
 
@@ -1,70 +1,175 @@
-use crate::heap_dump::{AnalysisClassInfo, HeapDump, InstanceInfo, Reference, FAKE_ROOT_ID};
+mod attribution;
+mod class;
+mod class_hierarchy;
+mod class_loaders;
+mod duplicate_classes;
+mod duplicate_strings;
+mod event_listeners;
+mod findings;
+mod object;
+mod packages;
+mod pagination;
+mod players;
+mod plugin_dependencies;
+mod scheduler;
+mod worlds;
+
+use crate::analysis::plugins::{plugin_usage, PluginUsage};
+use crate::heap_dump::{HeapDump, ReferenceFilter, FAKE_ROOT_ID};
 use crate::AppRoute;
+use class::{ClassLink, ClassPage};
+use class_hierarchy::ClassHierarchyTree;
+use class_loaders::ClassLoaderTable;
+use duplicate_classes::DuplicateClassTable;
+use duplicate_strings::DuplicateStringTable;
+use event_listeners::ListenerTable;
+use findings::FindingList;
 use hprof_rs::hprof_model::U8;
 use itertools::Itertools;
-use mini_moka::unsync::Cache;
+use object::{ObjectLink, ObjectPage};
+use packages::PackageTree;
+use pagination::{use_pagination, Paginated};
 use patternfly_yew::prelude::{
-    use_table_data, Cell, CellContext, MemoizedTableModel, Navigation, Pagination,
-    PaginationPosition, Tab, Table, TableColumn, TableEntryRenderer, TableHeader, TableMode, Tabs,
-    Toolbar, ToolbarContent, ToolbarItem, ToolbarItemType, UseTableData,
+    use_table_data, Cell, CellContext, MemoizedTableModel, Switch, Tab, Table, TableColumn,
+    TableEntryRenderer, TableHeader, TableHeaderSortBy, TableMode, Tabs, UseTableData,
 };
-use petgraph::algo;
-use std::collections::HashMap;
+use players::PlayerTable;
+use plugin_dependencies::PluginDependencyGraph;
+use scheduler::TaskTable;
+use std::cmp::Ordering;
 use std::rc::Rc;
+use worlds::WorldTable;
+use yew::events::MouseEvent;
 use yew::function_component;
 use yew::html;
 use yew::html_nested;
 use yew::use_callback;
 use yew::use_memo;
 use yew::use_state_eq;
+use yew::Callback;
 use yew::Html;
 use yew::Properties;
-use yew_router::hooks::use_location;
+use yew_router::hooks::{use_location, use_route};
 use yew_router::prelude::use_navigator;
 use yew_router::Routable;
 
 #[derive(Default, Clone, PartialEq, Routable)]
-enum AnalysisRoutes {
+pub(crate) enum AnalysisRoutes {
     #[default]
     #[at("/view")]
     Overview,
     #[at("/view/plugins")]
     Plugins,
+    #[at("/view/object/:id")]
+    Object { id: U8 },
+    #[at("/view/class/:id")]
+    Class { id: U8 },
 }
 
 #[function_component(ViewHeapDump)]
 pub(crate) fn view() -> Html {
     let loc = use_location().unwrap();
     let navigator = use_navigator().unwrap();
+    let route = use_route::<AnalysisRoutes>().unwrap_or_default();
 
     let selected = use_state_eq(|| 1);
     let onselect = use_callback(selected.clone(), |index, selected| selected.set(index));
 
-    if let Some(state) = loc.state::<HeapDump>() {
+    if let Some(state) = loc.state::<Rc<HeapDump>>() {
+        let heap_dump: Rc<HeapDump> = (*state).clone();
+        match route {
+            AnalysisRoutes::Object { id } => {
+                return html!(<ObjectPage key={id.to_string()} {heap_dump} object_id={id} />);
+            }
+            AnalysisRoutes::Class { id } => {
+                return html!(<ClassPage key={id.to_string()} {heap_dump} class_object_id={id} />);
+            }
+            AnalysisRoutes::Overview | AnalysisRoutes::Plugins => {}
+        }
         html!(
             <>
             <Tabs<usize> selected={*selected} {onselect}>
                 <Tab<usize> index=1 title="Overview">
                     { "This heap dump was created at " }
-                    { state.created_at.format("%Y-%m-%d %H:%M:%S").to_string() }
-                    <ClassTable heap_dump={state.clone()}/>
+                    { heap_dump.created_at.format("%Y-%m-%d %H:%M:%S").to_string() }
+                    { " on " }{ heap_dump.platform().platform.label() }
+                    <ClassTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=2 title="Class Hierarchy">
+                    <ClassHierarchyTree heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=3 title="Packages">
+                    <PackageTree heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=4 title="Class Loaders">
+                    <ClassLoaderTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=5 title="Duplicate Classes">
+                    <DuplicateClassTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=6 title="Duplicate Strings">
+                    <DuplicateStringTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=7 title="Plugins">
+                    <PluginTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=8 title="Plugin Dependencies">
+                    <PluginDependencyGraph heap_dump={heap_dump.clone()}/>
                 </Tab<usize>>
-                <Tab<usize> index=2 title="Plugins">
-                    <PluginTable heap_dump={state.clone()}/>
+                <Tab<usize> index=9 title="Event Listeners">
+                    <ListenerTable heap_dump={heap_dump.clone()}/>
                 </Tab<usize>>
-                <Tab<usize> index=4 title="Memory Usage Bugs">
-                    <MemoryBugs heap_dump={state.clone()}/>
+                <Tab<usize> index=10 title="Scheduled Tasks">
+                    <TaskTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=11 title="Players">
+                    <PlayerTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=12 title="Worlds">
+                    <WorldTable heap_dump={heap_dump.clone()}/>
+                </Tab<usize>>
+                <Tab<usize> index=13 title="Findings">
+                    <FindingList heap_dump={heap_dump.clone()}/>
                 </Tab<usize>>
             </Tabs<usize>>
         </>
         )
     } else {
         log::info!("redirecting to / as no state is present");
-        navigator.replace(&AppRoute::Upload);
+        // remember the requested page, so deep links work once the heap dump is loaded
+        let target = [("target", loc.path())];
+        if let Err(err) = navigator.replace_with_query(&AppRoute::Upload, &target) {
+            log::warn!("failed to remember {}: {}", loc.path(), err);
+            navigator.replace(&AppRoute::Upload);
+        }
         html!(<></>)
     }
 }
 
+#[derive(PartialEq, Properties)]
+pub(crate) struct AnalysisLinkProps {
+    pub heap_dump: Rc<HeapDump>,
+    pub to: AnalysisRoutes,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A link to another analysis page. Unlike a router link, it passes the heap dump
+/// along as location state, as the analysis pages cannot be rendered without it.
+#[function_component(AnalysisLink)]
+pub(crate) fn analysis_link(props: &AnalysisLinkProps) -> Html {
+    let navigator = use_navigator().unwrap();
+    let onclick = {
+        let heap_dump = props.heap_dump.clone();
+        let to = props.to.clone();
+        Callback::from(move |event: MouseEvent| {
+            event.prevent_default();
+            navigator.push_with_state(&to, heap_dump.clone());
+        })
+    };
+    html!(<a href={props.to.to_path()} {onclick}>{ props.children.clone() }</a>)
+}
+
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum ClassTableColumns {
     ClassName,
@@ -72,31 +177,31 @@ pub enum ClassTableColumns {
 }
 
 #[derive(Clone)]
-struct ClassTableEntry(String, usize);
+struct ClassTableEntry(Rc<HeapDump>, U8, usize);
 
 impl TableEntryRenderer<ClassTableColumns> for ClassTableEntry {
     fn render_cell(&self, context: CellContext<'_, ClassTableColumns>) -> Cell {
         match context.column {
-            ClassTableColumns::ClassName => html!({ self.0.clone() }),
-            ClassTableColumns::InstanceCount => html!(self.1),
+            ClassTableColumns::ClassName => {
+                html!(<ClassLink heap_dump={self.0.clone()} class_object_id={self.1} />)
+            }
+            ClassTableColumns::InstanceCount => html!(self.2),
         }
         .into()
     }
 }
 
 #[derive(PartialEq, Properties)]
-struct Props {
-    heap_dump: Rc<HeapDump>,
+pub(crate) struct Props {
+    pub heap_dump: Rc<HeapDump>,
 }
 
 #[function_component(ClassTable)]
 fn class_table(props: &Props) -> Html {
-    let offset = use_state_eq(|| 0);
-    let limit = use_state_eq(|| 5);
-
     let size = props.heap_dump.classes.len().clone();
+    let pagination = use_pagination(size, &[5, 10, 25, 50, 100]);
 
-    let entries = use_memo((*offset, *limit), |(offset, limit)| {
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
         props
             .heap_dump
             .classes
@@ -105,12 +210,8 @@ fn class_table(props: &Props) -> Html {
             .take(*limit)
             .map(|class_info| {
                 ClassTableEntry(
-                    props
-                        .heap_dump
-                        .names
-                        .get(&class_info.class_name_id)
-                        .unwrap()
-                        .clone(),
+                    props.heap_dump.clone(),
+                    class_info.class_object_id,
                     props
                         .heap_dump
                         .objects_by_class
@@ -131,72 +232,60 @@ fn class_table(props: &Props) -> Html {
         </TableHeader<ClassTableColumns >>
     };
 
-    let total_entries = Some(size);
-
-    let limit_callback = use_callback(limit.clone(), |number, limit| limit.set(number));
-    let s = size;
-    let nav_callback = use_callback(
-        (offset.clone(), *limit),
-        move |page: Navigation, (offset, limit)| {
-            let o = match page {
-                Navigation::First => 0,
-                Navigation::Last => ((s - 1) / limit) * limit,
-                Navigation::Previous => **offset - limit,
-                Navigation::Next => **offset + limit,
-                Navigation::Page(n) => n * limit,
-            };
-            offset.set(o);
-        },
-    );
-
-    html! (
-        <>
-            <Toolbar>
-                <ToolbarContent>
-                    <ToolbarItem r#type={ToolbarItemType::Pagination}>
-                        <Pagination
-                            {total_entries}
-                            offset={*offset}
-                            entries_per_page_choices={vec![5, 10, 25, 50, 100]}
-                            selected_choice={*limit}
-                            onlimit={&limit_callback}
-                            onnavigation={&nav_callback}
-                        />
-                    </ToolbarItem>
-                </ToolbarContent>
-            </Toolbar>
+    html!(
+        <Paginated {pagination}>
             <Table<ClassTableColumns, UseTableData<ClassTableColumns, MemoizedTableModel<ClassTableEntry >>>
                 mode={TableMode::Compact}
                 {header}
                 {entries}
             />
-            <Pagination
-                {total_entries}
-                offset={*offset}
-                entries_per_page_choices={vec![5, 10, 25, 50, 100]}
-                selected_choice={*limit}
-                onlimit={&limit_callback}
-                onnavigation={&nav_callback}
-                position={PaginationPosition::Bottom}
-            />
-        </>
+        </Paginated>
     )
 }
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum PluginTableColumns {
+    Name,
+    Version,
+    Authors,
+    Dependencies,
     MainClassName,
     ClassCount,
+    InstanceCount,
+    ShallowSize,
+    RetainedSize,
 }
 
 #[derive(Clone)]
-struct PluginTableEntry(String, usize);
+struct PluginTableEntry(Rc<HeapDump>, PluginUsage);
 
 impl TableEntryRenderer<PluginTableColumns> for PluginTableEntry {
     fn render_cell(&self, context: CellContext<'_, PluginTableColumns>) -> Cell {
+        let usage = &self.1;
+        let description = &usage.plugin.description;
         match context.column {
-            PluginTableColumns::MainClassName => html!({ self.0.clone() }),
-            PluginTableColumns::ClassCount => html!(self.1),
+            PluginTableColumns::Name => html!(
+                <AnalysisLink heap_dump={self.0.clone()} to={AnalysisRoutes::Object { id: usage.plugin.object_id }}>
+                    { description.name.clone().unwrap_or_else(|| "<< unknown >>".to_string()) }
+                </AnalysisLink>
+            ),
+            PluginTableColumns::Version => html!({ description.version.clone().unwrap_or_default() }),
+            PluginTableColumns::Authors => html!({ description.authors.join(", ") }),
+            PluginTableColumns::Dependencies => html!({
+                description
+                    .depend
+                    .iter()
+                    .cloned()
+                    .chain(description.softdepend.iter().map(|name| format!("{} (soft)", name)))
+                    .join(", ")
+            }),
+            PluginTableColumns::MainClassName => html!(
+                <ClassLink heap_dump={self.0.clone()} class_object_id={usage.plugin.main_class_object_id} />
+            ),
+            PluginTableColumns::ClassCount => html!(usage.class_count),
+            PluginTableColumns::InstanceCount => html!(usage.instance_count),
+            PluginTableColumns::ShallowSize => html!(usage.shallow_size),
+            PluginTableColumns::RetainedSize => html!(usage.retained_size),
         }
         .into()
     }
@@ -204,47 +293,57 @@ impl TableEntryRenderer<PluginTableColumns> for PluginTableEntry {
 
 #[function_component(PluginTable)]
 fn plugin_table(props: &Props) -> Html {
-    let mut is_plugin_class_cache: Cache<U8, bool> = Cache::builder().max_capacity(512).build();
-    let classes = &props.heap_dump.classes;
-    let names = &props.heap_dump.names;
-    let plugin_instances = props
-        .heap_dump
-        .objects
-        .values()
-        .filter_map(|reference| match &**reference {
-            Reference::Instance(instance) => Some(instance),
-            Reference::ObjectArray(_) => None,
-            Reference::PrimitiveArray(_) => None,
-            Reference::FakeCommonRoot => None,
-        })
-        .filter(|instance| is_plugin_class(instance, &mut is_plugin_class_cache, classes, names))
-        .collect::<Vec<_>>();
-
-    let by_loader = classes
-        .values()
-        .into_grouping_map_by(|class| class.class_loader_object_id)
-        .collect::<Vec<_>>();
-
-    let offset = use_state_eq(|| 0);
-    let limit = use_state_eq(|| 5);
+    let usages = use_memo(props.heap_dump.clone(), |heap_dump| {
+        plugin_usage(heap_dump, ReferenceFilter::StrongOnly)
+    });
 
-    let size = plugin_instances.len();
+    let sortby = use_state_eq(|| None);
+    let onsort = use_callback(
+        sortby.clone(),
+        |sort: TableHeaderSortBy<PluginTableColumns>, sortby| sortby.set(Some(sort)),
+    );
 
-    let entries = use_memo((*offset, *limit), |(offset, limit)| {
-        plugin_instances
-            .iter()
+    let size = usages.len();
+    let pagination = use_pagination(size, &[5, 10, 25, 50, 100]);
+    let (offset, limit) = (pagination.offset, pagination.limit);
+
+    let entries = use_memo((offset, limit, *sortby), |(offset, limit, sortby)| {
+        let mut usages = (*usages).clone();
+        if let Some(sortby) = sortby {
+            usages.sort_by(|a, b| {
+                let ordering = match sortby.index {
+                    PluginTableColumns::Name => {
+                        a.plugin.description.name.cmp(&b.plugin.description.name)
+                    }
+                    PluginTableColumns::Version => a
+                        .plugin
+                        .description
+                        .version
+                        .cmp(&b.plugin.description.version),
+                    PluginTableColumns::Authors | PluginTableColumns::Dependencies => {
+                        Ordering::Equal
+                    }
+                    PluginTableColumns::MainClassName => props
+                        .heap_dump
+                        .class_name(a.plugin.main_class_object_id)
+                        .cmp(props.heap_dump.class_name(b.plugin.main_class_object_id)),
+                    PluginTableColumns::ClassCount => a.class_count.cmp(&b.class_count),
+                    PluginTableColumns::InstanceCount => a.instance_count.cmp(&b.instance_count),
+                    PluginTableColumns::ShallowSize => a.shallow_size.cmp(&b.shallow_size),
+                    PluginTableColumns::RetainedSize => a.retained_size.cmp(&b.retained_size),
+                };
+                if sortby.asc {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+        usages
+            .into_iter()
             .skip(*offset)
             .take(*limit)
-            .filter_map(|instance| classes.get(&instance.class_object_id))
-            .map(|class_info| {
-                PluginTableEntry(
-                    names.get(&class_info.class_name_id).unwrap().clone(),
-                    by_loader
-                        .get(&class_info.class_loader_object_id)
-                        .map(|v| v.len())
-                        .unwrap_or(0),
-                )
-            })
+            .map(|usage| PluginTableEntry(props.heap_dump.clone(), usage))
             .collect::<Vec<_>>()
     });
 
@@ -252,171 +351,76 @@ fn plugin_table(props: &Props) -> Html {
 
     let header = html_nested! {
         <TableHeader<PluginTableColumns>>
-            <TableColumn<PluginTableColumns> label="Main Class" index={PluginTableColumns::MainClassName} />
-            <TableColumn<PluginTableColumns> label="Loaded Classes (by same classloader)" index={PluginTableColumns::ClassCount} />
+            <TableColumn<PluginTableColumns> label="Name" index={PluginTableColumns::Name} onsort={onsort.clone()} sortby={*sortby} />
+            <TableColumn<PluginTableColumns> label="Version" index={PluginTableColumns::Version} onsort={onsort.clone()} sortby={*sortby} />
+            <TableColumn<PluginTableColumns> label="Authors" index={PluginTableColumns::Authors} />
+            <TableColumn<PluginTableColumns> label="Dependencies" index={PluginTableColumns::Dependencies} />
+            <TableColumn<PluginTableColumns> label="Main Class" index={PluginTableColumns::MainClassName} onsort={onsort.clone()} sortby={*sortby} />
+            <TableColumn<PluginTableColumns> label="Loaded Classes (by same classloader)" index={PluginTableColumns::ClassCount} onsort={onsort.clone()} sortby={*sortby} />
+            <TableColumn<PluginTableColumns> label="Instances" index={PluginTableColumns::InstanceCount} onsort={onsort.clone()} sortby={*sortby} />
+            <TableColumn<PluginTableColumns> label="Shallow Size" index={PluginTableColumns::ShallowSize} onsort={onsort.clone()} sortby={*sortby} />
+            <TableColumn<PluginTableColumns> label="Retained Size" index={PluginTableColumns::RetainedSize} onsort={onsort.clone()} sortby={*sortby} />
         </TableHeader<PluginTableColumns>>
     };
 
-    let total_entries = Some(size);
-
-    let limit_callback = use_callback(limit.clone(), |number, limit| limit.set(number));
-    let s = size;
-    let nav_callback = use_callback(
-        (offset.clone(), *limit),
-        move |page: Navigation, (offset, limit)| {
-            let o = match page {
-                Navigation::First => 0,
-                Navigation::Last => ((s - 1) / limit) * limit,
-                Navigation::Previous => **offset - limit,
-                Navigation::Next => **offset + limit,
-                Navigation::Page(n) => n * limit,
-            };
-            offset.set(o);
-        },
-    );
-
-    html! (
-        <>
-            <Toolbar>
-                <ToolbarContent>
-                    <ToolbarItem r#type={ToolbarItemType::Pagination}>
-                        <Pagination
-                            {total_entries}
-                            offset={*offset}
-                            entries_per_page_choices={vec![5, 10, 25, 50, 100]}
-                            selected_choice={*limit}
-                            onlimit={&limit_callback}
-                            onnavigation={&nav_callback}
-                        />
-                    </ToolbarItem>
-                </ToolbarContent>
-            </Toolbar>
+    html!(
+        <Paginated {pagination}>
             <Table<PluginTableColumns, UseTableData<PluginTableColumns, MemoizedTableModel<PluginTableEntry>>>
                 mode={TableMode::Compact}
                 {header}
                 {entries}
             />
-            <Pagination
-                {total_entries}
-                offset={*offset}
-                entries_per_page_choices={vec![5, 10, 25, 50, 100]}
-                selected_choice={*limit}
-                onlimit={&limit_callback}
-                onnavigation={&nav_callback}
-                position={PaginationPosition::Bottom}
-            />
-        </>
+        </Paginated>
     )
 }
 
-fn is_plugin_class(
-    instance_info: &InstanceInfo,
-    is_plugin_class_cache: &mut Cache<U8, bool>,
-    classes: &HashMap<U8, AnalysisClassInfo>,
-    names: &HashMap<U8, String>,
-) -> bool {
-    let mut class_id = &instance_info.class_object_id;
-
-    if let Some(b) = is_plugin_class_cache.get(class_id) {
-        *b // cache hit
-    } else {
-        // collect all superclasses in this stack
-        let mut stack = Vec::new();
-        while let Some(class_info) = classes.get(class_id) {
-            stack.push(*class_id);
-            if is_java_plugin_class_exact(names, &class_info) {
-                for x in stack {
-                    is_plugin_class_cache.insert(x, true);
-                }
-                return true;
-            }
-            class_id = &class_info.super_class_object_id;
-            if let Some(&b) = is_plugin_class_cache.get(class_id) {
-                // found superclass in cache, remember for all seen classes
-                for x in stack {
-                    is_plugin_class_cache.insert(x, b);
-                }
-                return b;
-            }
-        }
-        for x in stack {
-            is_plugin_class_cache.insert(x, false);
-        }
-        false
-    }
-}
-
-fn is_java_plugin_class_exact(names: &HashMap<U8, String>, class_info: &AnalysisClassInfo) -> bool {
-    names
-        .get(&class_info.class_name_id)
-        .unwrap_or(&"<< no name >>".to_string())
-        == "org/bukkit/plugin/java/JavaPlugin"
-}
-
-fn is_craft_player_class(names: &HashMap<U8, String>, class_info: &AnalysisClassInfo) -> bool {
-    let no_name = "<< no name >>".to_string();
-    let name = names.get(&class_info.class_name_id).unwrap_or(&no_name);
-    name == "org/bukkit/craftbukkit/entity/CraftPlayer"
+#[derive(PartialEq, Properties)]
+struct ReferencePathProps {
+    heap_dump: Rc<HeapDump>,
+    /// the objects on the path, starting at a GC root
+    path: Vec<U8>,
 }
 
-#[function_component(MemoryBugs)]
-fn memory_bugs(props: &Props) -> Html {
+/// Lists the objects on a path from a GC root together with the field,
+/// array index or root kind each one is referenced by.
+#[function_component(ReferencePath)]
+fn reference_path(props: &ReferencePathProps) -> Html {
     let heap_dump = &props.heap_dump;
-    let names = &props.heap_dump.names;
-    log::info!("number of classes: {}", &props.heap_dump.classes.len());
-    let class = &props
-        .heap_dump
-        .classes
-        .values()
-        .find(|class| is_craft_player_class(names, class))
-        .expect("CraftPlayer class not loaded?");
-
-    let object_graph = &props.heap_dump.object_graph;
-
-    let empty_vec = Vec::new();
-    let player_instances = props
-        .heap_dump
-        .objects_by_class
-        .get_vec(&class.class_object_id)
-        .unwrap_or(&empty_vec);
-
-    let rc = FAKE_ROOT_ID;
-    let mut leaking_instances = Vec::new();
-    for player_instance in player_instances {
-        let x = match &**player_instance {
-            Reference::Instance(instance_info) => instance_info,
-            _ => panic!("player instance is not an instance"),
-        };
-        let mut paths =
-            algo::all_simple_paths::<Vec<_>, _>(object_graph, rc.clone(), x.object_id, 1, None);
-        let referenced_by_mc = paths.all(|path| {
-            path.iter().any(|reference| {
-                let reference = heap_dump.objects.get(reference);
-                match reference {
-                    Some(thing) => {
-                        let x = &**thing;
-                        match x {
-                            Reference::Instance(instance_info) => {
-                                is_loaded_by_mc(&instance_info, &heap_dump)
-                            }
-                            Reference::ObjectArray(_) => false, // TODO who loads array classes?
-                            _ => false,
-                        }
-                    }
-                    None => false,
-                }
-            })
+    let steps = std::iter::once(FAKE_ROOT_ID)
+        .chain(props.path.iter().copied())
+        .tuple_windows()
+        .map(|(from, to)| {
+            let via = heap_dump
+                .object_graph
+                .edge_weight(from, to)
+                .map(|edge| heap_dump.describe_edges(from, to, edge))
+                .unwrap_or_default();
+            html!(
+                <li>
+                    <ObjectLink heap_dump={heap_dump.clone()} object_id={to} />
+                    { " via " }
+                    <code>{ via }</code>
+                </li>
+            )
         });
-        if !referenced_by_mc {
-            leaking_instances.push(player_instance.clone())
-        }
-    }
+    html!(<ol>{ for steps }</ol>)
+}
 
-    html!(<>{leaking_instances.len()}</>)
+#[derive(PartialEq, Properties)]
+struct ReferenceFilterSwitchProps {
+    filter: ReferenceFilter,
+    onchange: Callback<ReferenceFilter>,
 }
 
-fn is_loaded_by_mc(instance_info: &InstanceInfo, heap_dump: &HeapDump) -> bool {
-    let class_id = instance_info.class_object_id;
-    let class: Option<&AnalysisClassInfo> = heap_dump.classes.get(&class_id);
-    class.filter(|c| c.class_loader_object_id != 0u64).is_some() // TODO add (other?) allowed classloaders
+/// Toggles between following all references and only the strong ones.
+#[function_component(ReferenceFilterSwitch)]
+fn reference_filter_switch(props: &ReferenceFilterSwitchProps) -> Html {
+    let onchange = props.onchange.reform(ReferenceFilter::from_strong_only);
+    html!(
+        <Switch
+            checked={props.filter == ReferenceFilter::StrongOnly}
+            label="Ignore soft, weak, final and phantom references"
+            {onchange}
+        />
+    )
 }
@@ -0,0 +1,36 @@
+use crate::analysis::attribution::Attribution;
+use crate::heap_dump::HeapDump;
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::object::ObjectLink;
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::Html;
+use yew::Properties;
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct AttributionProps {
+    pub heap_dump: Rc<HeapDump>,
+    pub attribution: Attribution,
+}
+
+/// The responsible plugin together with the object and field through which it holds on to something.
+#[function_component(AttributionView)]
+pub(crate) fn attribution_view(props: &AttributionProps) -> Html {
+    let heap_dump = &props.heap_dump;
+    let attribution = &props.attribution;
+    html!(
+        <>
+            <ClassLink
+                heap_dump={heap_dump.clone()}
+                class_object_id={attribution.plugin_main_class_object_id}
+            />
+            { " through " }
+            <ObjectLink heap_dump={heap_dump.clone()} object_id={attribution.holder_object_id} />
+            if let Some(edge) = &attribution.edge {
+                { " via " }
+                <code>{ heap_dump.describe_edge(edge) }</code>
+            }
+        </>
+    )
+}
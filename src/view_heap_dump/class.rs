@@ -0,0 +1,167 @@
+use crate::heap_dump::{super_classes, HeapDump, ReferenceFilter};
+use crate::view_heap_dump::class_loaders::LoaderLink;
+use crate::view_heap_dump::object::{ContentTable, ObjectLink};
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::{AnalysisLink, AnalysisRoutes};
+use hprof_rs::hprof_model::U8;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, DescriptionGroup, DescriptionList, MemoizedTableModel,
+    Table, TableColumn, TableEntryRenderer, TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+use yew::Properties;
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct ClassProps {
+    pub heap_dump: Rc<HeapDump>,
+    pub class_object_id: U8,
+}
+
+/// A link to the page of a class, labelled with its name.
+#[function_component(ClassLink)]
+pub(crate) fn class_link(props: &ClassProps) -> Html {
+    html!(
+        <AnalysisLink heap_dump={props.heap_dump.clone()} to={AnalysisRoutes::Class { id: props.class_object_id }}>
+            { props.heap_dump.class_name(props.class_object_id) }
+        </AnalysisLink>
+    )
+}
+
+/// Shows the hierarchy, class loader, fields and instances of a class.
+#[function_component(ClassPage)]
+pub(crate) fn class_page(props: &ClassProps) -> Html {
+    let heap_dump = &props.heap_dump;
+    let class_object_id = props.class_object_id;
+    let Some(class_info) = heap_dump.classes.get(&class_object_id) else {
+        return html!(<p>{ format!("There is no class with id {:#x}.", class_object_id) }</p>);
+    };
+
+    let super_classes = super_classes(&heap_dump.classes, class_object_id)
+        .skip(1)
+        .map(|super_class| {
+            html!(<li><ClassLink heap_dump={heap_dump.clone()} class_object_id={super_class.class_object_id} /></li>)
+        });
+    let subclasses = heap_dump
+        .subclasses(class_object_id)
+        .into_iter()
+        .map(|subclass| {
+            html!(<li><ClassLink heap_dump={heap_dump.clone()} class_object_id={subclass} /></li>)
+        })
+        .collect::<Vec<_>>();
+    let instance_fields = class_info
+        .instance_field_name_ids
+        .iter()
+        .map(|name_id| html!(<li><code>{ heap_dump.name(*name_id) }</code></li>));
+
+    html!(
+        <>
+            <AnalysisLink heap_dump={heap_dump.clone()} to={AnalysisRoutes::Overview}>
+                { "Back to overview" }
+            </AnalysisLink>
+            <h2>{ "class " }{ heap_dump.class_name(class_object_id) }</h2>
+            <DescriptionList>
+                <DescriptionGroup term="Class object">
+                    <ObjectLink heap_dump={heap_dump.clone()} object_id={class_object_id} />
+                </DescriptionGroup>
+                <DescriptionGroup term="Defining class loader">
+                    <LoaderLink heap_dump={heap_dump.clone()} object_id={class_info.class_loader_object_id} />
+                </DescriptionGroup>
+                <DescriptionGroup term="Superclasses">
+                    <ol>{ for super_classes }</ol>
+                </DescriptionGroup>
+                <DescriptionGroup term="Known subclasses">
+                    if subclasses.is_empty() {
+                        { "none" }
+                    } else {
+                        <ul>{ for subclasses }</ul>
+                    }
+                </DescriptionGroup>
+                <DescriptionGroup term="Declared instance fields">
+                    <ul>{ for instance_fields }</ul>
+                </DescriptionGroup>
+            </DescriptionList>
+            <h3>{ "Static fields" }</h3>
+            <ContentTable heap_dump={heap_dump.clone()} object_id={class_object_id} />
+            <h3>{ "Instances" }</h3>
+            <InstanceTable heap_dump={heap_dump.clone()} {class_object_id} />
+        </>
+    )
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum InstanceTableColumns {
+    Object,
+    ShallowSize,
+    RetainedSize,
+}
+
+#[derive(Clone)]
+struct InstanceTableEntry {
+    heap_dump: Rc<HeapDump>,
+    object_id: U8,
+}
+
+impl TableEntryRenderer<InstanceTableColumns> for InstanceTableEntry {
+    fn render_cell(&self, context: CellContext<'_, InstanceTableColumns>) -> Cell {
+        match context.column {
+            InstanceTableColumns::Object => {
+                html!(<ObjectLink heap_dump={self.heap_dump.clone()} object_id={self.object_id} />)
+            }
+            InstanceTableColumns::ShallowSize => html!(self.heap_dump.shallow_size(self.object_id)),
+            InstanceTableColumns::RetainedSize => html!(self
+                .heap_dump
+                .retained_size(self.object_id, ReferenceFilter::StrongOnly)
+                .map(|size| size.to_string())
+                .unwrap_or_else(|| "not reachable".to_string())),
+        }
+        .into()
+    }
+}
+
+#[function_component(InstanceTable)]
+fn instance_table(props: &ClassProps) -> Html {
+    let instances = props
+        .heap_dump
+        .objects_by_class
+        .get_vec(&props.class_object_id);
+    let size = instances.map(|instances| instances.len()).unwrap_or(0);
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        instances
+            .into_iter()
+            .flatten()
+            .skip(*offset)
+            .take(*limit)
+            .map(|instance| InstanceTableEntry {
+                heap_dump: props.heap_dump.clone(),
+                object_id: instance.object_id(),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<InstanceTableColumns>>
+            <TableColumn<InstanceTableColumns> label="Object" index={InstanceTableColumns::Object} />
+            <TableColumn<InstanceTableColumns> label="Shallow Size" index={InstanceTableColumns::ShallowSize} />
+            <TableColumn<InstanceTableColumns> label="Retained Size" index={InstanceTableColumns::RetainedSize} />
+        </TableHeader<InstanceTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<InstanceTableColumns, UseTableData<InstanceTableColumns, MemoizedTableModel<InstanceTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
@@ -0,0 +1,121 @@
+use crate::analysis::class_hierarchy::ClassHierarchy;
+use crate::heap_dump::HeapDump;
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::Props;
+use hprof_rs::hprof_model::U8;
+use patternfly_yew::prelude::{
+    Cell, CellContext, TableColumn, TableMode, TreeNode, TreeTable, TreeTableHeader, TreeTableModel,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ClassHierarchyColumns {
+    ClassName,
+    InstanceCount,
+    InclusiveInstanceCount,
+    ShallowSize,
+    InclusiveShallowSize,
+}
+
+struct ClassHierarchyModel {
+    heap_dump: Rc<HeapDump>,
+    hierarchy: Rc<ClassHierarchy>,
+}
+
+impl ClassHierarchyModel {
+    fn node(&self, class_object_id: U8) -> Rc<dyn TreeNode<ClassHierarchyColumns>> {
+        Rc::new(ClassNode {
+            heap_dump: self.heap_dump.clone(),
+            hierarchy: self.hierarchy.clone(),
+            class_object_id,
+        })
+    }
+}
+
+impl PartialEq for ClassHierarchyModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.heap_dump == other.heap_dump
+    }
+}
+
+impl TreeTableModel<ClassHierarchyColumns> for ClassHierarchyModel {
+    fn children(&self) -> Vec<Rc<dyn TreeNode<ClassHierarchyColumns>>> {
+        self.hierarchy
+            .roots
+            .iter()
+            .map(|root| self.node(*root))
+            .collect()
+    }
+}
+
+struct ClassNode {
+    heap_dump: Rc<HeapDump>,
+    hierarchy: Rc<ClassHierarchy>,
+    class_object_id: U8,
+}
+
+impl TreeNode<ClassHierarchyColumns> for ClassNode {
+    fn render_main(&self) -> Cell {
+        html!(<ClassLink heap_dump={self.heap_dump.clone()} class_object_id={self.class_object_id} />)
+            .into()
+    }
+
+    fn render_cell(&self, context: CellContext<'_, ClassHierarchyColumns>) -> Cell {
+        let stats = self.hierarchy.stats(self.class_object_id);
+        match context.column {
+            ClassHierarchyColumns::ClassName => return self.render_main(),
+            ClassHierarchyColumns::InstanceCount => html!(stats.instance_count),
+            ClassHierarchyColumns::InclusiveInstanceCount => html!(stats.inclusive_instance_count),
+            ClassHierarchyColumns::ShallowSize => html!(stats.shallow_size),
+            ClassHierarchyColumns::InclusiveShallowSize => html!(stats.inclusive_shallow_size),
+        }
+        .into()
+    }
+
+    fn children(&self) -> Vec<Rc<dyn TreeNode<ClassHierarchyColumns>>> {
+        self.hierarchy
+            .subclasses(self.class_object_id)
+            .iter()
+            .map(|subclass| -> Rc<dyn TreeNode<ClassHierarchyColumns>> {
+                Rc::new(ClassNode {
+                    heap_dump: self.heap_dump.clone(),
+                    hierarchy: self.hierarchy.clone(),
+                    class_object_id: *subclass,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The inheritance tree of all classes, with instance counts and sizes per class
+/// and summed up over all subclasses.
+#[function_component(ClassHierarchyTree)]
+pub(crate) fn class_hierarchy_tree(props: &Props) -> Html {
+    let model = use_memo(props.heap_dump.clone(), |heap_dump| ClassHierarchyModel {
+        heap_dump: heap_dump.clone(),
+        hierarchy: Rc::new(ClassHierarchy::new(heap_dump)),
+    });
+
+    let header = html_nested! {
+        <TreeTableHeader<ClassHierarchyColumns>>
+            <TableColumn<ClassHierarchyColumns> label="Class Name" index={ClassHierarchyColumns::ClassName} />
+            <TableColumn<ClassHierarchyColumns> label="Instances" index={ClassHierarchyColumns::InstanceCount} />
+            <TableColumn<ClassHierarchyColumns> label="Instances (incl. subclasses)" index={ClassHierarchyColumns::InclusiveInstanceCount} />
+            <TableColumn<ClassHierarchyColumns> label="Shallow Size" index={ClassHierarchyColumns::ShallowSize} />
+            <TableColumn<ClassHierarchyColumns> label="Shallow Size (incl. subclasses)" index={ClassHierarchyColumns::InclusiveShallowSize} />
+        </TreeTableHeader<ClassHierarchyColumns>>
+    };
+
+    html!(
+        <TreeTable<ClassHierarchyColumns, ClassHierarchyModel>
+            mode={TableMode::Compact}
+            {header}
+            {model}
+        />
+    )
+}
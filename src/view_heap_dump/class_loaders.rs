@@ -0,0 +1,113 @@
+use crate::analysis::class_loaders::{class_loaders, ClassLoaderInfo, BOOTSTRAP_LOADER_ID};
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::object::{ObjectLink, ObjectProps};
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::Props;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, MemoizedTableModel, Table, TableColumn, TableEntryRenderer,
+    TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+/// A link to a class loader instance, or a label for the bootstrap class loader.
+#[function_component(LoaderLink)]
+pub(crate) fn loader_link(props: &ObjectProps) -> Html {
+    if props.object_id == BOOTSTRAP_LOADER_ID {
+        html!({ "bootstrap class loader" })
+    } else {
+        html!(<ObjectLink heap_dump={props.heap_dump.clone()} object_id={props.object_id} />)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ClassLoaderTableColumns {
+    Loader,
+    LoaderClass,
+    Parent,
+    DefinedClasses,
+    InstanceCount,
+    ShallowSize,
+    RetainedSize,
+}
+
+#[derive(Clone)]
+struct ClassLoaderTableEntry(Rc<HeapDump>, ClassLoaderInfo);
+
+impl TableEntryRenderer<ClassLoaderTableColumns> for ClassLoaderTableEntry {
+    fn render_cell(&self, context: CellContext<'_, ClassLoaderTableColumns>) -> Cell {
+        let heap_dump = &self.0;
+        let loader = &self.1;
+        match context.column {
+            ClassLoaderTableColumns::Loader => {
+                html!(<LoaderLink heap_dump={heap_dump.clone()} object_id={loader.object_id} />)
+            }
+            ClassLoaderTableColumns::LoaderClass => match heap_dump.class_of(loader.object_id) {
+                Some(class_object_id) => {
+                    html!(<ClassLink heap_dump={heap_dump.clone()} {class_object_id} />)
+                }
+                None => html!(),
+            },
+            ClassLoaderTableColumns::Parent => match loader.parent_object_id {
+                Some(parent) => {
+                    html!(<LoaderLink heap_dump={heap_dump.clone()} object_id={parent} />)
+                }
+                None => html!(),
+            },
+            ClassLoaderTableColumns::DefinedClasses => html!(loader.defined_classes.len()),
+            ClassLoaderTableColumns::InstanceCount => html!(loader.instance_count),
+            ClassLoaderTableColumns::ShallowSize => html!(loader.shallow_size),
+            ClassLoaderTableColumns::RetainedSize => html!(loader.retained_size),
+        }
+        .into()
+    }
+}
+
+/// Lists all class loaders with the number of classes they defined and the memory
+/// used by instances of these classes.
+#[function_component(ClassLoaderTable)]
+pub(crate) fn class_loader_table(props: &Props) -> Html {
+    let loaders = use_memo(props.heap_dump.clone(), |heap_dump| {
+        class_loaders(heap_dump, ReferenceFilter::StrongOnly)
+    });
+    let size = loaders.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        loaders
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|loader| ClassLoaderTableEntry(props.heap_dump.clone(), loader.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<ClassLoaderTableColumns>>
+            <TableColumn<ClassLoaderTableColumns> label="Class Loader" index={ClassLoaderTableColumns::Loader} />
+            <TableColumn<ClassLoaderTableColumns> label="Loader Class" index={ClassLoaderTableColumns::LoaderClass} />
+            <TableColumn<ClassLoaderTableColumns> label="Parent" index={ClassLoaderTableColumns::Parent} />
+            <TableColumn<ClassLoaderTableColumns> label="Defined Classes" index={ClassLoaderTableColumns::DefinedClasses} />
+            <TableColumn<ClassLoaderTableColumns> label="Instances" index={ClassLoaderTableColumns::InstanceCount} />
+            <TableColumn<ClassLoaderTableColumns> label="Shallow Size" index={ClassLoaderTableColumns::ShallowSize} />
+            <TableColumn<ClassLoaderTableColumns> label="Retained Size" index={ClassLoaderTableColumns::RetainedSize} />
+        </TableHeader<ClassLoaderTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<ClassLoaderTableColumns, UseTableData<ClassLoaderTableColumns, MemoizedTableModel<ClassLoaderTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
@@ -0,0 +1,89 @@
+use crate::analysis::duplicate_classes::{duplicate_classes, DuplicateClass};
+use crate::heap_dump::HeapDump;
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::class_loaders::LoaderLink;
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::Props;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, MemoizedTableModel, Table, TableColumn, TableEntryRenderer,
+    TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum DuplicateClassTableColumns {
+    ClassName,
+    LoaderCount,
+    Copies,
+}
+
+#[derive(Clone)]
+struct DuplicateClassTableEntry(Rc<HeapDump>, DuplicateClass);
+
+impl TableEntryRenderer<DuplicateClassTableColumns> for DuplicateClassTableEntry {
+    fn render_cell(&self, context: CellContext<'_, DuplicateClassTableColumns>) -> Cell {
+        let heap_dump = &self.0;
+        let duplicate = &self.1;
+        match context.column {
+            DuplicateClassTableColumns::ClassName => html!({ duplicate.name.clone() }),
+            DuplicateClassTableColumns::LoaderCount => html!(duplicate.loader_count()),
+            DuplicateClassTableColumns::Copies => html!(
+                <ul>
+                    { for duplicate.copies.iter().map(|copy| html!(
+                        <li>
+                            <ClassLink heap_dump={heap_dump.clone()} class_object_id={copy.class_object_id} />
+                            { " defined by " }
+                            <LoaderLink heap_dump={heap_dump.clone()} object_id={copy.class_loader_object_id} />
+                            { format!(" ({} instances)", copy.instance_count) }
+                        </li>
+                    )) }
+                </ul>
+            ),
+        }
+        .into()
+    }
+}
+
+/// Lists classes that were defined by more than one class loader.
+#[function_component(DuplicateClassTable)]
+pub(crate) fn duplicate_class_table(props: &Props) -> Html {
+    let duplicates = use_memo(props.heap_dump.clone(), |heap_dump| {
+        duplicate_classes(heap_dump)
+    });
+    let size = duplicates.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        duplicates
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|duplicate| DuplicateClassTableEntry(props.heap_dump.clone(), duplicate.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<DuplicateClassTableColumns>>
+            <TableColumn<DuplicateClassTableColumns> label="Class Name" index={DuplicateClassTableColumns::ClassName} />
+            <TableColumn<DuplicateClassTableColumns> label="Class Loaders" index={DuplicateClassTableColumns::LoaderCount} />
+            <TableColumn<DuplicateClassTableColumns> label="Copies" index={DuplicateClassTableColumns::Copies} />
+        </TableHeader<DuplicateClassTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<DuplicateClassTableColumns, UseTableData<DuplicateClassTableColumns, MemoizedTableModel<DuplicateClassTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
@@ -0,0 +1,99 @@
+use crate::analysis::duplicate_strings::{duplicate_strings, DuplicateString};
+use crate::heap_dump::HeapDump;
+use crate::view_heap_dump::object::ObjectLink;
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::Props;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, MemoizedTableModel, Table, TableColumn, TableEntryRenderer,
+    TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+/// Longer values are cut off.
+const MAX_VALUE_LENGTH: usize = 200;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum DuplicateStringTableColumns {
+    Value,
+    InstanceCount,
+    WastedBytes,
+    Example,
+}
+
+#[derive(Clone)]
+struct DuplicateStringTableEntry(Rc<HeapDump>, DuplicateString);
+
+impl TableEntryRenderer<DuplicateStringTableColumns> for DuplicateStringTableEntry {
+    fn render_cell(&self, context: CellContext<'_, DuplicateStringTableColumns>) -> Cell {
+        let heap_dump = &self.0;
+        let duplicate = &self.1;
+        match context.column {
+            DuplicateStringTableColumns::Value => {
+                let mut value = duplicate
+                    .value
+                    .chars()
+                    .take(MAX_VALUE_LENGTH)
+                    .collect::<String>();
+                if duplicate.value.chars().count() > MAX_VALUE_LENGTH {
+                    value.push('…');
+                }
+                html!(<code>{ format!("{:?}", value) }</code>)
+            }
+            DuplicateStringTableColumns::InstanceCount => html!(duplicate.string_object_ids.len()),
+            DuplicateStringTableColumns::WastedBytes => html!(duplicate.wasted_bytes),
+            DuplicateStringTableColumns::Example => match duplicate.string_object_ids.first() {
+                Some(object_id) => {
+                    html!(<ObjectLink heap_dump={heap_dump.clone()} object_id={*object_id} />)
+                }
+                None => html!(),
+            },
+        }
+        .into()
+    }
+}
+
+/// Lists string values that are held by several `String` instances, e.g. to decide where
+/// interning pays off.
+#[function_component(DuplicateStringTable)]
+pub(crate) fn duplicate_string_table(props: &Props) -> Html {
+    let duplicates = use_memo(props.heap_dump.clone(), |heap_dump| {
+        duplicate_strings(heap_dump)
+    });
+    let size = duplicates.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        duplicates
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|duplicate| DuplicateStringTableEntry(props.heap_dump.clone(), duplicate.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<DuplicateStringTableColumns>>
+            <TableColumn<DuplicateStringTableColumns> label="Value" index={DuplicateStringTableColumns::Value} />
+            <TableColumn<DuplicateStringTableColumns> label="Instances" index={DuplicateStringTableColumns::InstanceCount} />
+            <TableColumn<DuplicateStringTableColumns> label="Wasted Bytes" index={DuplicateStringTableColumns::WastedBytes} />
+            <TableColumn<DuplicateStringTableColumns> label="Example" index={DuplicateStringTableColumns::Example} />
+        </TableHeader<DuplicateStringTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<DuplicateStringTableColumns, UseTableData<DuplicateStringTableColumns, MemoizedTableModel<DuplicateStringTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
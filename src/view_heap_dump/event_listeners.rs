@@ -0,0 +1,106 @@
+use crate::analysis::event_listeners::{listener_groups, ListenerGroup};
+use crate::heap_dump::HeapDump;
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::object::ObjectLink;
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::Props;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, MemoizedTableModel, Table, TableColumn, TableEntryRenderer,
+    TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ListenerTableColumns {
+    Event,
+    Plugin,
+    Registrations,
+    RedundantRegistrations,
+}
+
+#[derive(Clone)]
+struct ListenerTableEntry(Rc<HeapDump>, ListenerGroup);
+
+impl TableEntryRenderer<ListenerTableColumns> for ListenerTableEntry {
+    fn render_cell(&self, context: CellContext<'_, ListenerTableColumns>) -> Cell {
+        let heap_dump = &self.0;
+        let group = &self.1;
+        match context.column {
+            ListenerTableColumns::Event => match group.event_class_object_id {
+                Some(class_object_id) => {
+                    html!(<ClassLink heap_dump={heap_dump.clone()} {class_object_id} />)
+                }
+                None => html!(
+                    <ObjectLink heap_dump={heap_dump.clone()} object_id={group.handler_list_object_id} />
+                ),
+            },
+            ListenerTableColumns::Plugin => html!(
+                <>
+                    if let Some(class_object_id) = group.plugin_main_class_object_id {
+                        <ClassLink heap_dump={heap_dump.clone()} {class_object_id} />
+                    } else {
+                        <ObjectLink heap_dump={heap_dump.clone()} object_id={group.plugin_object_id} />
+                    }
+                    if !group.plugin_enabled {
+                        <strong>{ " (disabled)" }</strong>
+                    }
+                </>
+            ),
+            ListenerTableColumns::Registrations => html!(group.registrations.len()),
+            ListenerTableColumns::RedundantRegistrations => html!(
+                if group.redundant_registrations > 0 {
+                    <strong>{ group.redundant_registrations }</strong>
+                } else {
+                    { 0 }
+                }
+            ),
+        }
+        .into()
+    }
+}
+
+/// Lists the event listeners registered in Bukkit's handler lists, by event type and plugin.
+/// Listeners of disabled plugins and listener classes registered more than once are highlighted.
+#[function_component(ListenerTable)]
+pub(crate) fn listener_table(props: &Props) -> Html {
+    let groups = use_memo(props.heap_dump.clone(), |heap_dump| {
+        listener_groups(heap_dump)
+    });
+    let size = groups.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        groups
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|group| ListenerTableEntry(props.heap_dump.clone(), group.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<ListenerTableColumns>>
+            <TableColumn<ListenerTableColumns> label="Event" index={ListenerTableColumns::Event} />
+            <TableColumn<ListenerTableColumns> label="Plugin" index={ListenerTableColumns::Plugin} />
+            <TableColumn<ListenerTableColumns> label="Registered Listeners" index={ListenerTableColumns::Registrations} />
+            <TableColumn<ListenerTableColumns> label="Redundant Registrations" index={ListenerTableColumns::RedundantRegistrations} />
+        </TableHeader<ListenerTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<ListenerTableColumns, UseTableData<ListenerTableColumns, MemoizedTableModel<ListenerTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
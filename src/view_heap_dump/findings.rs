@@ -0,0 +1,133 @@
+use crate::analysis::detectors::{run_detectors, DetectorSettings, Finding};
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use crate::view_heap_dump::attribution::AttributionView;
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::object::ObjectLink;
+use crate::view_heap_dump::{Props, ReferenceFilterSwitch, ReferencePath};
+use itertools::Itertools;
+use patternfly_yew::prelude::{Button, ButtonVariant, Form, FormGroup, InputGroup, TextInput};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::use_callback;
+use yew::use_memo;
+use yew::use_state_eq;
+use yew::Html;
+use yew::Properties;
+
+/// Runs all applicable detectors and lists their findings.
+#[function_component(FindingList)]
+pub(crate) fn finding_list(props: &Props) -> Html {
+    let filter = use_state_eq(ReferenceFilter::default);
+    let onchange = use_callback(filter.clone(), |value, filter| filter.set(value));
+    let filter = *filter;
+
+    let heap_dump = &props.heap_dump;
+    // the detectors only run again once the edited patterns are applied
+    let applied_trusted_loaders = use_state_eq(|| {
+        DetectorSettings::for_platform(heap_dump.platform())
+            .trusted_loaders
+            .join(", ")
+    });
+    let trusted_loaders = use_state_eq(|| (*applied_trusted_loaders).clone());
+    let ontrustedloaders = use_callback(trusted_loaders.clone(), |value, trusted_loaders| {
+        trusted_loaders.set(value)
+    });
+    let onapply = use_callback(
+        (trusted_loaders.clone(), applied_trusted_loaders.clone()),
+        |_, (trusted_loaders, applied_trusted_loaders)| {
+            applied_trusted_loaders.set((**trusted_loaders).clone())
+        },
+    );
+
+    let results = use_memo(
+        (heap_dump.clone(), filter, (*applied_trusted_loaders).clone()),
+        |(heap_dump, filter, trusted_loaders)| {
+            let settings = DetectorSettings {
+                trusted_loaders: trusted_loaders
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|pattern| !pattern.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            };
+            run_detectors(heap_dump, *filter, &settings)
+        },
+    );
+
+    html!(
+        <>
+            <ReferenceFilterSwitch {filter} {onchange} />
+            <Form>
+                <FormGroup label="Trusted class loaders (comma separated, * matches any characters)">
+                    <InputGroup>
+                        <TextInput value={(*trusted_loaders).clone()} onchange={ontrustedloaders} />
+                        <Button
+                            variant={ButtonVariant::Control}
+                            disabled={*trusted_loaders == *applied_trusted_loaders}
+                            onclick={onapply}
+                        >
+                            {"Apply"}
+                        </Button>
+                    </InputGroup>
+                </FormGroup>
+            </Form>
+            { for results.iter().map(|result| html!(
+                <>
+                    <h2>{ result.name }</h2>
+                    if result.findings.is_empty() {
+                        { "Nothing found" }
+                    }
+                    { for result.findings.iter().map(|finding| html!(
+                        <FindingView heap_dump={heap_dump.clone()} finding={finding.clone()} />
+                    )) }
+                </>
+            )) }
+        </>
+    )
+}
+
+#[derive(PartialEq, Properties)]
+struct FindingProps {
+    heap_dump: Rc<HeapDump>,
+    finding: Finding,
+}
+
+/// A single finding with its suspects and the path keeping each of them alive.
+#[function_component(FindingView)]
+fn finding_view(props: &FindingProps) -> Html {
+    let heap_dump = &props.heap_dump;
+    let finding = &props.finding;
+    html!(
+        <>
+            <h3>{ format!("{}: {}", finding.severity.label(), finding.title) }</h3>
+            <p>{ finding.description.clone() }</p>
+            <ul>
+                { for finding.suspects.iter().map(|suspect| html!(
+                    <li>
+                        <ObjectLink heap_dump={heap_dump.clone()} object_id={suspect.object_id} />
+                        if let Some(retained_size) = suspect.retained_size {
+                            { format!(", retaining {} bytes", retained_size) }
+                        }
+                        if !suspect.plugins.is_empty() {
+                            { ", held by " }
+                            { for Itertools::intersperse(
+                                suspect.plugins.iter().map(|class_object_id| html!(
+                                    <ClassLink heap_dump={heap_dump.clone()} class_object_id={*class_object_id} />
+                                )),
+                                html!({ ", " }),
+                            ) }
+                        }
+                        if let Some(attribution) = suspect.attribution.clone() {
+                            { ", attributed to " }
+                            <AttributionView heap_dump={heap_dump.clone()} {attribution} />
+                        }
+                        if let Some(path) = suspect.path.clone() {
+                            <ReferencePath heap_dump={heap_dump.clone()} {path} />
+                        }
+                    </li>
+                )) }
+            </ul>
+        </>
+    )
+}
@@ -0,0 +1,325 @@
+use crate::analysis::attribution::attribute;
+use crate::analysis::plugins::plugins_by_loader;
+use crate::heap_dump::{format_primitive, Edge, HeapDump, Reference, ReferenceFilter};
+use crate::view_heap_dump::attribution::AttributionView;
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::{AnalysisLink, AnalysisRoutes, ReferenceFilterSwitch, ReferencePath};
+use hprof_rs::hprof_model::{Value, U8};
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, DescriptionGroup, DescriptionList, MemoizedTableModel,
+    Table, TableColumn, TableEntryRenderer, TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_callback;
+use yew::use_memo;
+use yew::use_state_eq;
+use yew::Html;
+use yew::Properties;
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct ObjectProps {
+    pub heap_dump: Rc<HeapDump>,
+    pub object_id: U8,
+}
+
+/// A link to the page of an object, labelled with its type, id and a preview of its value.
+#[function_component(ObjectLink)]
+pub(crate) fn object_link(props: &ObjectProps) -> Html {
+    html!(
+        <AnalysisLink heap_dump={props.heap_dump.clone()} to={AnalysisRoutes::Object { id: props.object_id }}>
+            { props.heap_dump.describe_object(props.object_id) }
+        </AnalysisLink>
+    )
+}
+
+/// Only this many elements of an array are shown on the object page.
+const MAX_ARRAY_ELEMENTS: usize = 1000;
+
+/// Shows everything known about a single object: its sizes, field values or array elements,
+/// a path from a GC root and the objects referencing it.
+#[function_component(ObjectPage)]
+pub(crate) fn object_page(props: &ObjectProps) -> Html {
+    let filter = use_state_eq(ReferenceFilter::default);
+    let onchange = use_callback(filter.clone(), |value, filter| filter.set(value));
+    let filter = *filter;
+
+    let heap_dump = &props.heap_dump;
+    let object_id = props.object_id;
+    let path = use_memo(filter, |filter| heap_dump.path_to_root(object_id, *filter));
+    let plugins = use_memo(heap_dump.clone(), |heap_dump| plugins_by_loader(heap_dump));
+    let attribution = use_memo((path.clone(), filter), |(path, filter)| {
+        let path = path.as_deref().unwrap_or_default();
+        attribute(heap_dump, &plugins, object_id, path, *filter)
+    });
+    let retained_size = heap_dump
+        .retained_size(object_id, filter)
+        .map(|size| format!("{} bytes", size))
+        .unwrap_or_else(|| "not reachable".to_string());
+
+    html!(
+        <>
+            <AnalysisLink heap_dump={heap_dump.clone()} to={AnalysisRoutes::Overview}>
+                { "Back to overview" }
+            </AnalysisLink>
+            <h2>{ heap_dump.describe_object(object_id) }</h2>
+            <ReferenceFilterSwitch {filter} {onchange} />
+            <DescriptionList>
+                <DescriptionGroup term="Type">
+                    if let Some(class_object_id) = heap_dump.class_of(object_id) {
+                        <ClassLink heap_dump={heap_dump.clone()} {class_object_id} />
+                    } else {
+                        { heap_dump.type_name(object_id) }
+                    }
+                </DescriptionGroup>
+                <DescriptionGroup term="Shallow size">
+                    { heap_dump.shallow_size(object_id) }{ " bytes" }
+                </DescriptionGroup>
+                <DescriptionGroup term="Retained size">{ retained_size }</DescriptionGroup>
+                if let Some(root_kind) = heap_dump.root_kind(object_id) {
+                    <DescriptionGroup term="GC root">{ root_kind.label() }</DescriptionGroup>
+                }
+                if let Some(attribution) = (*attribution).clone() {
+                    <DescriptionGroup term="Responsible plugin">
+                        <AttributionView heap_dump={heap_dump.clone()} {attribution} />
+                    </DescriptionGroup>
+                }
+            </DescriptionList>
+            <h3>{ "Contents" }</h3>
+            <ContentTable heap_dump={heap_dump.clone()} {object_id} />
+            <h3>{ "Path to GC root" }</h3>
+            if let Some(path) = &*path {
+                <ReferencePath heap_dump={heap_dump.clone()} path={path.clone()} />
+            } else {
+                <p>{ "This object is not reachable from any GC root." }</p>
+            }
+            <h3>{ "Referrers" }</h3>
+            <ReferrerTable heap_dump={heap_dump.clone()} {object_id} />
+        </>
+    )
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ContentTableColumns {
+    Name,
+    DeclaringClass,
+    Value,
+}
+
+/// A field of an instance, a static field of a class or an array element.
+#[derive(Clone)]
+struct ContentTableEntry {
+    name: String,
+    declaring_class: String,
+    value: Html,
+}
+
+impl TableEntryRenderer<ContentTableColumns> for ContentTableEntry {
+    fn render_cell(&self, context: CellContext<'_, ContentTableColumns>) -> Cell {
+        match context.column {
+            ContentTableColumns::Name => html!(<code>{ self.name.clone() }</code>),
+            ContentTableColumns::DeclaringClass => html!({ self.declaring_class.clone() }),
+            ContentTableColumns::Value => self.value.clone(),
+        }
+        .into()
+    }
+}
+
+#[function_component(ContentTable)]
+pub(crate) fn content_table(props: &ObjectProps) -> Html {
+    let heap_dump = &props.heap_dump;
+    let object_id = props.object_id;
+
+    let entries = use_memo(object_id, |object_id| {
+        content_entries(heap_dump, *object_id)
+    });
+    let length = match heap_dump
+        .objects
+        .get(&object_id)
+        .map(|reference| &**reference)
+    {
+        Some(Reference::ObjectArray(array)) => array.values.len(),
+        Some(Reference::PrimitiveArray(array)) => array.values.len(),
+        _ => 0,
+    };
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<ContentTableColumns>>
+            <TableColumn<ContentTableColumns> label="Name" index={ContentTableColumns::Name} />
+            <TableColumn<ContentTableColumns> label="Declared in" index={ContentTableColumns::DeclaringClass} />
+            <TableColumn<ContentTableColumns> label="Value" index={ContentTableColumns::Value} />
+        </TableHeader<ContentTableColumns>>
+    };
+
+    html!(
+        <>
+            if length > MAX_ARRAY_ELEMENTS {
+                <p>{ format!("Showing the first {} of {} elements.", MAX_ARRAY_ELEMENTS, length) }</p>
+            }
+            <Table<ContentTableColumns, UseTableData<ContentTableColumns, MemoizedTableModel<ContentTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </>
+    )
+}
+
+/// The static fields of a class, the fields of an instance or the elements of an array.
+fn content_entries(heap_dump: &Rc<HeapDump>, object_id: U8) -> Vec<ContentTableEntry> {
+    if let Some(class_info) = heap_dump.classes.get(&object_id) {
+        return class_info
+            .static_fields
+            .iter()
+            .map(|field| ContentTableEntry {
+                name: heap_dump.name(field.name_id).to_string(),
+                declaring_class: heap_dump.class_name(object_id).to_string(),
+                value: render_value(heap_dump, &field.value),
+            })
+            .collect();
+    }
+    match heap_dump
+        .objects
+        .get(&object_id)
+        .map(|reference| &**reference)
+    {
+        Some(Reference::Instance(instance)) => {
+            let field_layout = heap_dump.field_layout(instance.class_object_id);
+            instance
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, value)| match field_layout.get(index) {
+                    Some(field) => ContentTableEntry {
+                        name: heap_dump.name(field.name_id).to_string(),
+                        declaring_class: heap_dump
+                            .class_name(field.declaring_class_object_id)
+                            .to_string(),
+                        value: render_value(heap_dump, value),
+                    },
+                    None => ContentTableEntry {
+                        name: format!("<field #{}>", index),
+                        declaring_class: String::new(),
+                        value: render_value(heap_dump, value),
+                    },
+                })
+                .collect()
+        }
+        Some(Reference::ObjectArray(array)) => array
+            .values
+            .iter()
+            .take(MAX_ARRAY_ELEMENTS)
+            .enumerate()
+            .map(|(index, object_id)| ContentTableEntry {
+                name: format!("[{}]", index),
+                declaring_class: String::new(),
+                value: render_reference(heap_dump, *object_id),
+            })
+            .collect(),
+        Some(Reference::PrimitiveArray(array)) => array
+            .values
+            .iter()
+            .take(MAX_ARRAY_ELEMENTS)
+            .enumerate()
+            .map(|(index, value)| ContentTableEntry {
+                name: format!("[{}]", index),
+                declaring_class: String::new(),
+                value: render_value(heap_dump, value),
+            })
+            .collect(),
+        Some(Reference::FakeCommonRoot) | None => Vec::new(),
+    }
+}
+
+fn render_value(heap_dump: &Rc<HeapDump>, value: &Value) -> Html {
+    match value {
+        Value::Object { object_id } => render_reference(heap_dump, *object_id),
+        Value::Array { object_id } => render_reference(heap_dump, *object_id),
+        value => html!(format_primitive(value)),
+    }
+}
+
+fn render_reference(heap_dump: &Rc<HeapDump>, object_id: U8) -> Html {
+    if object_id == 0 {
+        html!(<code>{ "null" }</code>)
+    } else {
+        html!(<ObjectLink heap_dump={heap_dump.clone()} {object_id} />)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ReferrerTableColumns {
+    Referrer,
+    Field,
+}
+
+#[derive(Clone)]
+struct ReferrerTableEntry {
+    heap_dump: Rc<HeapDump>,
+    referrer: U8,
+    object_id: U8,
+    edge: Edge,
+}
+
+impl TableEntryRenderer<ReferrerTableColumns> for ReferrerTableEntry {
+    fn render_cell(&self, context: CellContext<'_, ReferrerTableColumns>) -> Cell {
+        match context.column {
+            ReferrerTableColumns::Referrer => {
+                html!(<ObjectLink heap_dump={self.heap_dump.clone()} object_id={self.referrer} />)
+            }
+            ReferrerTableColumns::Field => {
+                let via = self
+                    .heap_dump
+                    .describe_edges(self.referrer, self.object_id, &self.edge);
+                html!(<code>{ via }</code>)
+            }
+        }
+        .into()
+    }
+}
+
+#[function_component(ReferrerTable)]
+fn referrer_table(props: &ObjectProps) -> Html {
+    let referrers = use_memo(props.object_id, |object_id| {
+        props.heap_dump.referrers(*object_id)
+    });
+    let size = referrers.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        referrers
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|(referrer, edge)| ReferrerTableEntry {
+                heap_dump: props.heap_dump.clone(),
+                referrer: *referrer,
+                object_id: props.object_id,
+                edge: *edge,
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<ReferrerTableColumns>>
+            <TableColumn<ReferrerTableColumns> label="Referrer" index={ReferrerTableColumns::Referrer} />
+            <TableColumn<ReferrerTableColumns> label="Referenced by" index={ReferrerTableColumns::Field} />
+        </TableHeader<ReferrerTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<ReferrerTableColumns, UseTableData<ReferrerTableColumns, MemoizedTableModel<ReferrerTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
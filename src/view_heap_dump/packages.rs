@@ -0,0 +1,126 @@
+use crate::analysis::packages::{package_tree, Package};
+use crate::heap_dump::HeapDump;
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::Props;
+use hprof_rs::hprof_model::U8;
+use patternfly_yew::prelude::{
+    Cell, CellContext, TableColumn, TableMode, TreeNode, TreeTable, TreeTableHeader, TreeTableModel,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum PackageTreeColumns {
+    Name,
+    InstanceCount,
+    ShallowSize,
+}
+
+struct PackageTreeModel {
+    heap_dump: Rc<HeapDump>,
+    root: Rc<Package>,
+}
+
+impl PartialEq for PackageTreeModel {
+    fn eq(&self, other: &Self) -> bool {
+        self.heap_dump == other.heap_dump
+    }
+}
+
+impl TreeTableModel<PackageTreeColumns> for PackageTreeModel {
+    fn children(&self) -> Vec<Rc<dyn TreeNode<PackageTreeColumns>>> {
+        package_children(&self.heap_dump, &self.root)
+    }
+}
+
+enum PackageTreeNode {
+    Package(Rc<HeapDump>, Rc<Package>),
+    Class(Rc<HeapDump>, U8, usize, u64),
+}
+
+impl TreeNode<PackageTreeColumns> for PackageTreeNode {
+    fn render_main(&self) -> Cell {
+        match self {
+            PackageTreeNode::Package(_, package) => html!({ package.simple_name() }),
+            PackageTreeNode::Class(heap_dump, class_object_id, _, _) => {
+                html!(<ClassLink heap_dump={heap_dump.clone()} class_object_id={*class_object_id} />)
+            }
+        }
+        .into()
+    }
+
+    fn render_cell(&self, context: CellContext<'_, PackageTreeColumns>) -> Cell {
+        let (instance_count, shallow_size) = match self {
+            PackageTreeNode::Package(_, package) => (package.instance_count, package.shallow_size),
+            PackageTreeNode::Class(_, _, instance_count, shallow_size) => {
+                (*instance_count, *shallow_size)
+            }
+        };
+        match context.column {
+            PackageTreeColumns::Name => return self.render_main(),
+            PackageTreeColumns::InstanceCount => html!(instance_count),
+            PackageTreeColumns::ShallowSize => html!(shallow_size),
+        }
+        .into()
+    }
+
+    fn children(&self) -> Vec<Rc<dyn TreeNode<PackageTreeColumns>>> {
+        match self {
+            PackageTreeNode::Package(heap_dump, package) => package_children(heap_dump, package),
+            PackageTreeNode::Class(..) => Vec::new(),
+        }
+    }
+}
+
+/// The subpackages of a package followed by the classes directly in it.
+fn package_children(
+    heap_dump: &Rc<HeapDump>,
+    package: &Package,
+) -> Vec<Rc<dyn TreeNode<PackageTreeColumns>>> {
+    let packages = package.packages.iter().map(|package| {
+        Rc::new(PackageTreeNode::Package(heap_dump.clone(), package.clone()))
+            as Rc<dyn TreeNode<PackageTreeColumns>>
+    });
+    let classes = package
+        .classes
+        .iter()
+        .map(|(class_object_id, instance_count, shallow_size)| {
+            Rc::new(PackageTreeNode::Class(
+                heap_dump.clone(),
+                *class_object_id,
+                *instance_count,
+                *shallow_size,
+            )) as Rc<dyn TreeNode<PackageTreeColumns>>
+        });
+    packages.chain(classes).collect()
+}
+
+/// The class histogram aggregated by package, so the dominating libraries and
+/// plugins can be spotted at a glance.
+#[function_component(PackageTree)]
+pub(crate) fn package_tree_view(props: &Props) -> Html {
+    let model = use_memo(props.heap_dump.clone(), |heap_dump| PackageTreeModel {
+        heap_dump: heap_dump.clone(),
+        root: Rc::new(package_tree(heap_dump)),
+    });
+
+    let header = html_nested! {
+        <TreeTableHeader<PackageTreeColumns>>
+            <TableColumn<PackageTreeColumns> label="Package" index={PackageTreeColumns::Name} />
+            <TableColumn<PackageTreeColumns> label="Instances" index={PackageTreeColumns::InstanceCount} />
+            <TableColumn<PackageTreeColumns> label="Shallow Size" index={PackageTreeColumns::ShallowSize} />
+        </TreeTableHeader<PackageTreeColumns>>
+    };
+
+    html!(
+        <TreeTable<PackageTreeColumns, PackageTreeModel>
+            mode={TableMode::Compact}
+            {header}
+            {model}
+        />
+    )
+}
@@ -0,0 +1,100 @@
+use patternfly_yew::prelude::{
+    Navigation, Pagination, PaginationPosition, Toolbar, ToolbarContent, ToolbarItem,
+    ToolbarItemType,
+};
+use yew::function_component;
+use yew::hook;
+use yew::html;
+use yew::use_callback;
+use yew::use_state_eq;
+use yew::Callback;
+use yew::Html;
+use yew::Properties;
+
+/// The page of a table that is currently shown, see [use_pagination].
+#[derive(Clone, PartialEq)]
+pub(crate) struct PageState {
+    /// the index of the first entry on the page
+    pub offset: usize,
+    /// the number of entries per page
+    pub limit: usize,
+    total: usize,
+    choices: &'static [usize],
+    onlimit: Callback<usize>,
+    onnavigation: Callback<Navigation>,
+}
+
+/// Keeps track of the page shown by a table with `total` entries.
+/// The first of the page size `choices` is used initially.
+#[hook]
+pub(crate) fn use_pagination(total: usize, choices: &'static [usize]) -> PageState {
+    let offset = use_state_eq(|| 0usize);
+    let limit = use_state_eq(|| choices[0]);
+
+    let onlimit = use_callback(limit.clone(), |number, limit| limit.set(number));
+    let onnavigation = use_callback(
+        (offset.clone(), *limit, total),
+        |page: Navigation, (offset, limit, total)| {
+            let o = match page {
+                Navigation::First => 0,
+                Navigation::Last => (total.saturating_sub(1) / limit) * limit,
+                Navigation::Previous => offset.saturating_sub(*limit),
+                Navigation::Next => **offset + limit,
+                Navigation::Page(n) => n * limit,
+            };
+            offset.set(o);
+        },
+    );
+
+    PageState {
+        offset: *offset,
+        limit: *limit,
+        total,
+        choices,
+        onlimit,
+        onnavigation,
+    }
+}
+
+#[derive(PartialEq, Properties)]
+pub(crate) struct PaginatedProps {
+    pub pagination: PageState,
+    pub children: Html,
+}
+
+/// Shows the current page of a table (the children) between pagination controls.
+#[function_component(Paginated)]
+pub(crate) fn paginated(props: &PaginatedProps) -> Html {
+    let pagination = &props.pagination;
+    let total_entries = Some(pagination.total);
+    let entries_per_page_choices = pagination.choices.to_vec();
+
+    html!(
+        <>
+            <Toolbar>
+                <ToolbarContent>
+                    <ToolbarItem r#type={ToolbarItemType::Pagination}>
+                        <Pagination
+                            {total_entries}
+                            offset={pagination.offset}
+                            entries_per_page_choices={entries_per_page_choices.clone()}
+                            selected_choice={pagination.limit}
+                            onlimit={&pagination.onlimit}
+                            onnavigation={&pagination.onnavigation}
+                        />
+                    </ToolbarItem>
+                </ToolbarContent>
+            </Toolbar>
+            { props.children.clone() }
+            <Pagination
+                {total_entries}
+                offset={pagination.offset}
+                {entries_per_page_choices}
+                selected_choice={pagination.limit}
+                onlimit={&pagination.onlimit}
+                onnavigation={&pagination.onnavigation}
+                position={PaginationPosition::Bottom}
+            />
+        </>
+    )
+}
@@ -0,0 +1,98 @@
+use crate::analysis::players::{players, Player};
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use crate::view_heap_dump::object::ObjectLink;
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::Props;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, MemoizedTableModel, Table, TableColumn, TableEntryRenderer,
+    TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum PlayerTableColumns {
+    Player,
+    Name,
+    Uuid,
+    Online,
+    Entity,
+    RetainedSize,
+}
+
+#[derive(Clone)]
+struct PlayerTableEntry(Rc<HeapDump>, Player);
+
+impl TableEntryRenderer<PlayerTableColumns> for PlayerTableEntry {
+    fn render_cell(&self, context: CellContext<'_, PlayerTableColumns>) -> Cell {
+        let heap_dump = &self.0;
+        let player = &self.1;
+        match context.column {
+            PlayerTableColumns::Player => {
+                html!(<ObjectLink heap_dump={heap_dump.clone()} object_id={player.object_id} />)
+            }
+            PlayerTableColumns::Name => html!(player.name.clone().unwrap_or_default()),
+            PlayerTableColumns::Uuid => html!(player.uuid.clone().unwrap_or_default()),
+            PlayerTableColumns::Online => {
+                if player.online {
+                    html!("online")
+                } else {
+                    html!(<strong>{ "offline" }</strong>)
+                }
+            }
+            PlayerTableColumns::Entity => match player.handle_object_id {
+                Some(object_id) => html!(<ObjectLink heap_dump={heap_dump.clone()} {object_id} />),
+                None => html!(),
+            },
+            PlayerTableColumns::RetainedSize => html!(player.retained_size),
+        }
+        .into()
+    }
+}
+
+/// Lists all `CraftPlayer`s in the heap. Offline players that are still reachable come first,
+/// as they are usually leaked by a plugin.
+#[function_component(PlayerTable)]
+pub(crate) fn player_table(props: &Props) -> Html {
+    let players = use_memo(props.heap_dump.clone(), |heap_dump| {
+        players(heap_dump, ReferenceFilter::StrongOnly)
+    });
+    let size = players.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        players
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|player| PlayerTableEntry(props.heap_dump.clone(), player.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<PlayerTableColumns>>
+            <TableColumn<PlayerTableColumns> label="Player" index={PlayerTableColumns::Player} />
+            <TableColumn<PlayerTableColumns> label="Name" index={PlayerTableColumns::Name} />
+            <TableColumn<PlayerTableColumns> label="UUID" index={PlayerTableColumns::Uuid} />
+            <TableColumn<PlayerTableColumns> label="Online" index={PlayerTableColumns::Online} />
+            <TableColumn<PlayerTableColumns> label="Entity" index={PlayerTableColumns::Entity} />
+            <TableColumn<PlayerTableColumns> label="Retained Size" index={PlayerTableColumns::RetainedSize} />
+        </TableHeader<PlayerTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<PlayerTableColumns, UseTableData<PlayerTableColumns, MemoizedTableModel<PlayerTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
@@ -0,0 +1,146 @@
+use crate::analysis::plugin_dependencies::{plugin_graph, DependencyKind};
+use crate::view_heap_dump::object::ObjectLink;
+use crate::view_heap_dump::Props;
+use hprof_rs::hprof_model::U8;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use yew::function_component;
+use yew::html;
+use yew::use_memo;
+use yew::Html;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 600.0;
+const RADIUS: f64 = 240.0;
+const NODE_RADIUS: f64 = 6.0;
+
+/// Draws the declared dependencies between plugins together with the references between
+/// their objects that are not backed by a declared dependency, and lists all cross-plugin references.
+#[function_component(PluginDependencyGraph)]
+pub(crate) fn plugin_dependency_graph(props: &Props) -> Html {
+    let heap_dump = &props.heap_dump;
+    let graph = use_memo(heap_dump.clone(), |heap_dump| plugin_graph(heap_dump));
+
+    if graph.plugins.is_empty() {
+        return html!({ "No plugins found" });
+    }
+
+    let labels: HashMap<U8, String> = graph
+        .plugins
+        .iter()
+        .map(|plugin| {
+            let label = plugin.description.name.clone().unwrap_or_else(|| {
+                heap_dump
+                    .class_name(plugin.main_class_object_id)
+                    .to_string()
+            });
+            (plugin.object_id, label)
+        })
+        .collect();
+    // place the plugins on a circle
+    let positions: HashMap<U8, (f64, f64)> = graph
+        .plugins
+        .iter()
+        .enumerate()
+        .map(|(index, plugin)| {
+            let angle = TAU * index as f64 / graph.plugins.len() as f64;
+            let x = WIDTH / 2.0 + RADIUS * angle.cos();
+            let y = HEIGHT / 2.0 + RADIUS * angle.sin();
+            (plugin.object_id, (x, y))
+        })
+        .collect();
+
+    let dependencies = graph.dependencies.iter().map(|dependency| {
+        let dasharray = match dependency.kind {
+            DependencyKind::Depend => "",
+            DependencyKind::SoftDepend => "8 4",
+            DependencyKind::LoadBefore => "2 4",
+        };
+        arrow(
+            &positions,
+            dependency.from,
+            dependency.to,
+            "#06c",
+            dasharray,
+        )
+    });
+    let undeclared_references = graph
+        .references
+        .iter()
+        .filter(|reference| !reference.declared)
+        .map(|reference| arrow(&positions, reference.from, reference.to, "#c9190b", ""));
+    let nodes = graph.plugins.iter().map(|plugin| {
+        let (x, y) = positions[&plugin.object_id];
+        html!(
+            <g>
+                <circle cx={x.to_string()} cy={y.to_string()} r={NODE_RADIUS.to_string()} />
+                <text x={x.to_string()} y={(y - 2.0 * NODE_RADIUS).to_string()} text-anchor="middle">
+                    { labels[&plugin.object_id].clone() }
+                </text>
+            </g>
+        )
+    });
+
+    html!(
+        <>
+            <svg width={WIDTH.to_string()} height={HEIGHT.to_string()} viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)}>
+                <defs>
+                    <marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto">
+                        <path d="M 0 0 L 10 5 L 0 10 z" />
+                    </marker>
+                </defs>
+                { for dependencies }
+                { for undeclared_references }
+                { for nodes }
+            </svg>
+            <p>
+                { "Blue arrows are declared dependencies (solid: depend, dashed: softdepend, dotted: loadbefore), " }
+                { "red arrows are references between plugins that do not declare a dependency." }
+            </p>
+            <h3>{ "Cross-plugin references" }</h3>
+            <ul>
+                { for graph.references.iter().map(|reference| html!(
+                    <li>
+                        { format!(
+                            "{} → {}: {} references, e.g. ",
+                            labels[&reference.from], labels[&reference.to], reference.count
+                        ) }
+                        <ObjectLink heap_dump={heap_dump.clone()} object_id={reference.example.0} />
+                        { " → " }
+                        <ObjectLink heap_dump={heap_dump.clone()} object_id={reference.example.1} />
+                        if !reference.declared {
+                            <strong>{ " (no declared dependency)" }</strong>
+                        }
+                    </li>
+                )) }
+            </ul>
+        </>
+    )
+}
+
+/// A line between two plugins that ends with an arrow head just before the target.
+fn arrow(
+    positions: &HashMap<U8, (f64, f64)>,
+    from: U8,
+    to: U8,
+    color: &str,
+    dasharray: &str,
+) -> Html {
+    let (x1, y1) = positions[&from];
+    let (x2, y2) = positions[&to];
+    let length = (x2 - x1).hypot(y2 - y1).max(1.0);
+    let shorten = (NODE_RADIUS + 2.0) / length;
+    let x2 = x2 - (x2 - x1) * shorten;
+    let y2 = y2 - (y2 - y1) * shorten;
+    html!(
+        <line
+            x1={x1.to_string()}
+            y1={y1.to_string()}
+            x2={x2.to_string()}
+            y2={y2.to_string()}
+            stroke={color.to_string()}
+            stroke-dasharray={dasharray.to_string()}
+            marker-end="url(#arrow)"
+        />
+    )
+}
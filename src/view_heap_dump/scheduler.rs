@@ -0,0 +1,123 @@
+use crate::analysis::scheduler::{describe_period, scheduled_tasks, TaskGroup};
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::object::ObjectLink;
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::Props;
+use itertools::Itertools;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, MemoizedTableModel, Table, TableColumn, TableEntryRenderer,
+    TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum TaskTableColumns {
+    Plugin,
+    TaskClass,
+    TaskCount,
+    Periods,
+    RetainedSize,
+    LargestTask,
+}
+
+#[derive(Clone)]
+struct TaskTableEntry(Rc<HeapDump>, TaskGroup);
+
+impl TableEntryRenderer<TaskTableColumns> for TaskTableEntry {
+    fn render_cell(&self, context: CellContext<'_, TaskTableColumns>) -> Cell {
+        let heap_dump = &self.0;
+        let group = &self.1;
+        match context.column {
+            TaskTableColumns::Plugin => {
+                match (group.plugin_main_class_object_id, group.plugin_object_id) {
+                    (Some(class_object_id), _) => {
+                        html!(<ClassLink heap_dump={heap_dump.clone()} {class_object_id} />)
+                    }
+                    (None, Some(object_id)) => {
+                        html!(<ObjectLink heap_dump={heap_dump.clone()} {object_id} />)
+                    }
+                    (None, None) => html!(),
+                }
+            }
+            TaskTableColumns::TaskClass => match group.task_class_object_id {
+                Some(class_object_id) => {
+                    html!(<ClassLink heap_dump={heap_dump.clone()} {class_object_id} />)
+                }
+                None => html!(),
+            },
+            TaskTableColumns::TaskCount => html!(group.tasks.len()),
+            TaskTableColumns::Periods => html!({
+                group
+                    .tasks
+                    .iter()
+                    .filter_map(|task| task.period)
+                    .unique()
+                    .sorted()
+                    .map(describe_period)
+                    .join(", ")
+            }),
+            TaskTableColumns::RetainedSize => html!(group.retained_size),
+            TaskTableColumns::LargestTask => {
+                match group.tasks.iter().max_by_key(|task| task.retained_size) {
+                    Some(task) => html!(
+                        <ObjectLink
+                            heap_dump={heap_dump.clone()}
+                            object_id={task.runnable_object_id.unwrap_or(task.object_id)}
+                        />
+                    ),
+                    None => html!(),
+                }
+            }
+        }
+        .into()
+    }
+}
+
+/// Lists the tasks of the Bukkit scheduler by plugin and task class,
+/// with the memory captured by the tasks.
+#[function_component(TaskTable)]
+pub(crate) fn task_table(props: &Props) -> Html {
+    let groups = use_memo(props.heap_dump.clone(), |heap_dump| {
+        scheduled_tasks(heap_dump, ReferenceFilter::StrongOnly)
+    });
+    let size = groups.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        groups
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|group| TaskTableEntry(props.heap_dump.clone(), group.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<TaskTableColumns>>
+            <TableColumn<TaskTableColumns> label="Plugin" index={TaskTableColumns::Plugin} />
+            <TableColumn<TaskTableColumns> label="Task Class" index={TaskTableColumns::TaskClass} />
+            <TableColumn<TaskTableColumns> label="Tasks" index={TaskTableColumns::TaskCount} />
+            <TableColumn<TaskTableColumns> label="Periods" index={TaskTableColumns::Periods} />
+            <TableColumn<TaskTableColumns> label="Retained Size" index={TaskTableColumns::RetainedSize} />
+            <TableColumn<TaskTableColumns> label="Largest Task" index={TaskTableColumns::LargestTask} />
+        </TableHeader<TaskTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<TaskTableColumns, UseTableData<TaskTableColumns, MemoizedTableModel<TaskTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}
@@ -0,0 +1,104 @@
+use crate::analysis::worlds::{worlds, World};
+use crate::heap_dump::{HeapDump, ReferenceFilter};
+use crate::view_heap_dump::class::ClassLink;
+use crate::view_heap_dump::object::ObjectLink;
+use crate::view_heap_dump::pagination::{use_pagination, Paginated};
+use crate::view_heap_dump::Props;
+use patternfly_yew::prelude::{
+    use_table_data, Cell, CellContext, MemoizedTableModel, Table, TableColumn, TableEntryRenderer,
+    TableHeader, TableMode, UseTableData,
+};
+use std::rc::Rc;
+use yew::function_component;
+use yew::html;
+use yew::html_nested;
+use yew::use_memo;
+use yew::Html;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum WorldTableColumns {
+    World,
+    Name,
+    Chunks,
+    Entities,
+    EntityTypes,
+    BlockEntities,
+    RetainedSize,
+}
+
+#[derive(Clone)]
+struct WorldTableEntry(Rc<HeapDump>, World);
+
+impl TableEntryRenderer<WorldTableColumns> for WorldTableEntry {
+    fn render_cell(&self, context: CellContext<'_, WorldTableColumns>) -> Cell {
+        let heap_dump = &self.0;
+        let world = &self.1;
+        match context.column {
+            WorldTableColumns::World => html!(
+                <ObjectLink
+                    heap_dump={heap_dump.clone()}
+                    object_id={world.handle_object_id.unwrap_or(world.object_id)}
+                />
+            ),
+            WorldTableColumns::Name => html!(world.name.clone().unwrap_or_default()),
+            WorldTableColumns::Chunks => html!(world.chunk_count),
+            WorldTableColumns::Entities => html!(world.entity_count()),
+            WorldTableColumns::EntityTypes => html!(
+                <ul>
+                    { for world.entity_counts.iter().take(5).map(|(class_object_id, count)| html!(
+                        <li>
+                            { format!("{} ", count) }
+                            <ClassLink heap_dump={heap_dump.clone()} class_object_id={*class_object_id} />
+                        </li>
+                    )) }
+                </ul>
+            ),
+            WorldTableColumns::BlockEntities => html!(world.block_entity_count),
+            WorldTableColumns::RetainedSize => html!(world.retained_size),
+        }
+        .into()
+    }
+}
+
+/// Lists the worlds of the server with their loaded chunks, entities and block entities.
+#[function_component(WorldTable)]
+pub(crate) fn world_table(props: &Props) -> Html {
+    let worlds = use_memo(props.heap_dump.clone(), |heap_dump| {
+        worlds(heap_dump, ReferenceFilter::StrongOnly)
+    });
+    let size = worlds.len();
+    let pagination = use_pagination(size, &[10, 25, 50, 100]);
+
+    let entries = use_memo((pagination.offset, pagination.limit), |(offset, limit)| {
+        worlds
+            .iter()
+            .skip(*offset)
+            .take(*limit)
+            .map(|world| WorldTableEntry(props.heap_dump.clone(), world.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let (entries, _) = use_table_data(MemoizedTableModel::new(entries));
+
+    let header = html_nested! {
+        <TableHeader<WorldTableColumns>>
+            <TableColumn<WorldTableColumns> label="World" index={WorldTableColumns::World} />
+            <TableColumn<WorldTableColumns> label="Name" index={WorldTableColumns::Name} />
+            <TableColumn<WorldTableColumns> label="Chunks" index={WorldTableColumns::Chunks} />
+            <TableColumn<WorldTableColumns> label="Entities" index={WorldTableColumns::Entities} />
+            <TableColumn<WorldTableColumns> label="Most Common Entities" index={WorldTableColumns::EntityTypes} />
+            <TableColumn<WorldTableColumns> label="Block Entities" index={WorldTableColumns::BlockEntities} />
+            <TableColumn<WorldTableColumns> label="Retained Size" index={WorldTableColumns::RetainedSize} />
+        </TableHeader<WorldTableColumns>>
+    };
+
+    html!(
+        <Paginated {pagination}>
+            <Table<WorldTableColumns, UseTableData<WorldTableColumns, MemoizedTableModel<WorldTableEntry>>>
+                mode={TableMode::Compact}
+                {header}
+                {entries}
+            />
+        </Paginated>
+    )
+}